@@ -1,6 +1,5 @@
 use crate::codec::ComponentCodec;
-use crate::{iter::Visit, Component, Content, Style};
-use std::collections::VecDeque;
+use crate::{iter::StyledNode, Component, Content, Style};
 
 #[allow(unused_imports)]
 // only used in rustdoc -- https://github.com/rust-lang/rust/issues/79542
@@ -78,37 +77,21 @@ impl<const S: char> ComponentCodec for LegacyCodec<S> {
 
 	fn serialize(self, component: &Component) -> Self::EncodeOutput {
 		let mut out = String::new();
-		let mut styles = VecDeque::<&Style>::new();
 
-		for op in component.visit() {
-			match op {
-				Visit::Push(node) => {
-					styles.push_back(&node.style);
-
-					// Collect every active style into one value.
-					let mut active_style = Style::default();
-					for style in &styles {
-						active_style.merge(style);
-					}
-
-					let mut to_apply = node.style.clone();
-
-					// Color wipes all prior formatting, so we need to reapply it.
-					if let Some(color) = node.style.color {
-						out.extend([S, color.color_code()]);
-						to_apply.merge(&active_style);
-					}
-
-					apply_format_styles::<S>(&mut out, &to_apply);
-
-					if let Some(content) = node.shallow_text() {
-						out += content;
-					}
+		for StyledNode { node, style, .. } in component.iter_styled() {
+			// Color wipes all prior formatting, so we need to reapply it.
+			let to_apply = match node.style.color {
+				Some(color) => {
+					out.extend([S, color.color_code()]);
+					&style
 				}
+				None => &node.style,
+			};
 
-				Visit::Pop(_) => {
-					styles.pop_back();
-				}
+			apply_format_styles::<S>(&mut out, to_apply);
+
+			if let Some(content) = node.shallow_text() {
+				out += content;
 			}
 		}
 
@@ -160,7 +143,7 @@ impl<const S: char> ComponentCodec for LegacyCodec<S> {
 				)
 			});
 
-		Component::empty().with_extra(extra).flattened()
+		Component::empty().with_extra(extra).compact()
 	}
 }
 
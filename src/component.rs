@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::mem;
 
-use crate::{serial, Content, Style};
+use crate::render::{VirtualComponent, VirtualContent};
+use crate::{iter, serial, Content, HoverEvent, Style};
 
 /// A struct modeling a text component. Components are all styled, and hold children. When displayed,
 /// components are written to the output depth-first.
@@ -19,7 +20,7 @@ use crate::{serial, Content, Style};
 /// ## Unsupported Types
 /// The `selector` and `nbt` component types are both unsupported. This is because they cannot be
 /// rendered by the client, and have to instead be replaced with [text][Content::Text] components.
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[serde(from = "serial::SerialVessel", into = "serial::SerialVessel")]
 #[non_exhaustive]
 pub struct Component {
@@ -129,6 +130,48 @@ impl Component {
 		})
 	}
 
+	/// Creates a virtual component. Virtual components hold a renderer that computes their content
+	/// separately for each viewer, such as a player's ping or a relative timestamp. They are turned
+	/// into concrete components by a [VirtualRenderer][crate::render::VirtualRenderer].
+	///
+	/// Virtual components serialized without being rendered first are written out as empty nodes.
+	/// To provide a fallback, use [Self::deferred_or()].
+	///
+	/// # Examples
+	/// ```
+	/// # use typewheel::Component;
+	/// use typewheel::render::{ComponentRenderer, VirtualRenderer};
+	///
+	/// let component = Component::deferred(|name: &String| Component::text(name.clone()));
+	/// let rendered = VirtualRenderer.render(&component, &"Notch".to_string());
+	///
+	/// assert_eq!(rendered, Component::text("Notch"));
+	/// ```
+	#[inline]
+	pub fn deferred<Ctx: 'static>(renderer: impl VirtualComponent<Ctx>) -> Self {
+		Self::new(Content::Virtual(VirtualContent::new(renderer)))
+	}
+
+	/// Creates a virtual component with a fallback. The fallback is used in place of the rendered
+	/// content when the component is serialized directly, or rendered for a different context type.
+	/// See [Self::deferred()] for more information.
+	///
+	/// # Examples
+	/// ```
+	/// # use typewheel::{Component, codec::{ComponentCodec, JsonCodec}};
+	/// let component = Component::deferred_or(|ping: &u32| Component::text(ping.to_string()), "?");
+	/// assert_eq!(JsonCodec.serialize(&component).unwrap(), r#"{"extra":["?"]}"#);
+	/// ```
+	#[inline]
+	pub fn deferred_or<Ctx: 'static>(
+		renderer: impl VirtualComponent<Ctx>,
+		fallback: impl Into<Component>,
+	) -> Self {
+		Self::new(Content::Virtual(
+			VirtualContent::new(renderer).with_fallback(fallback),
+		))
+	}
+
 	/// Creates an empty component with no content. Empty components can still contain styles and
 	/// extras, but do not render anything themselves.
 	///
@@ -223,13 +266,58 @@ impl Component {
 		target
 	}
 
+	/// Gets the number of nodes in this component tree, including this component and all of its
+	/// descendants. This is the number of items yielded by [Self::iter()].
+	///
+	/// # Examples
+	/// ```
+	/// # use typewheel::Component;
+	/// let component = Component::text("a").with_extra([Component::text("b").with_extra(["c"])]);
+	/// assert_eq!(component.tree_size(), 3);
+	/// ```
+	pub fn tree_size(&self) -> usize {
+		iter::count(self, false, false)
+	}
+
 	/// Gets the number of content nodes in this component. `content_size() >= tree_size()` is
 	/// invariant.
 	///
 	/// The difference between the content and the tree size is that the content size includes the
 	/// arguments of [translation][Content::Translation] components.
+	///
+	/// # Examples
+	/// ```
+	/// # use typewheel::Component;
+	/// let component = Component::translate("a", ["b", "c"]).with_extra(["d"]);
+	/// assert_eq!(component.tree_size(), 2);
+	/// assert_eq!(component.content_size(), 4);
+	/// ```
 	pub fn content_size(&self) -> usize {
-		0
+		iter::count(self, true, false)
+	}
+
+	/// Gets the depth of this component tree: the number of levels of children below this
+	/// component. A component without children has a depth of 0.
+	///
+	/// # Examples
+	/// ```
+	/// # use typewheel::Component;
+	/// assert_eq!(Component::text("a").depth(), 0);
+	///
+	/// let component = Component::text("a").with_extra([Component::text("b").with_extra(["c"]), "d".into()]);
+	/// assert_eq!(component.depth(), 2);
+	/// ```
+	pub fn depth(&self) -> usize {
+		// Walked with an explicit stack, so that deep trees can't overflow the call stack.
+		let mut stack = vec![(self, 0)];
+		let mut depth = 0;
+
+		while let Some((node, level)) = stack.pop() {
+			depth = depth.max(level);
+			stack.extend(node.extra.iter().map(|child| (child, level + 1)));
+		}
+
+		depth
 	}
 
 	/// Gets the text of this component, excluding that of its children. If its [content]
@@ -253,32 +341,165 @@ impl Component {
 		}
 	}
 
-	/// Flattens a component if its body is [Content::Empty] by taking its first child and merging
-	/// it.
+	/// Compacts this component tree into an equivalent tree with fewer nodes and less redundant
+	/// styling. The result renders exactly the same way as the original, but usually serializes to
+	/// considerably less data. Compaction:
+	/// * removes style fields that repeat the value that a node inherits from its parent;
+	/// * moves style fields that are shared by all children of an empty node into that node;
+	/// * merges adjacent text nodes with the same style;
+	/// * removes [empty][Content::Empty] nodes that have no children, and unwraps empty nodes that
+	///   have no style or only a single child.
+	///
+	/// Translation arguments and hover text are compacted as separate trees.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::{Component, TextColor};
+	///
+	/// let component = Component::empty().with_extra([
+	///     Component::text("hello").with_color(TextColor::Red),
+	///     Component::empty(),
+	///     Component::text(" world").with_color(TextColor::Red),
+	///     Component::text("!").with_color(TextColor::Red).with_bold(true),
+	/// ]);
 	///
-	/// This might eventually get added as a public API, but it needs to be refined first.
-	#[doc(hidden)]
-	pub(crate) fn flattened(mut self) -> Self {
-		let Content::Empty = self.content else {
-			return self;
-		};
+	/// assert_eq!(
+	///     component.compact(),
+	///     Component::text("hello world")
+	///         .with_color(TextColor::Red)
+	///         .with_extra([Component::text("!").with_bold(true)])
+	/// );
+	/// ```
+	#[must_use]
+	pub fn compact(self) -> Self {
+		compact_node(self, &Style::BLANK)
+	}
+}
 
-		if self.extra.is_empty() {
-			return self;
+/// Compacts a node, given the effective style that it inherits.
+fn compact_node(node: Component, inherited: &Style) -> Component {
+	let Component {
+		content,
+		mut style,
+		extra,
+	} = node;
+
+	style.strip_inherited(inherited);
+	let mut effective = inherited.clone();
+	effective.merge(&style);
+
+	let mut children = Vec::with_capacity(extra.len());
+	for child in extra {
+		let child = compact_node(child, &effective);
+
+		if let Content::Empty = child.content {
+			if child.extra.is_empty() {
+				continue;
+			}
+
+			// A blank wrapper can be replaced by its children, which inherit the same style.
+			if child.style.is_blank() {
+				children.extend(child.extra);
+				continue;
+			}
 		}
 
-		let first = self.extra.swap_remove(0);
-		let mut style = self.style;
-		style.merge(&first.style);
+		children.push(child);
+	}
 
-		self.extra.extend(first.extra);
+	let mut node = Component::create(compact_nested(content), compact_hover(style), children);
+
+	// Hoisting styles is only safe if the node has no content of its own to apply them to.
+	if let (Content::Empty, [first, rest @ ..]) = (&node.content, node.extra.as_slice()) {
+		if !rest.is_empty() {
+			let mut shared = first.style.clone();
+			for child in rest {
+				shared.intersect(&child.style);
+			}
+
+			if !shared.is_blank() {
+				node.style.merge(&shared);
+				for child in &mut node.extra {
+					child.style.strip_inherited(&shared);
+				}
+			}
+		}
+	}
 
-		Self {
-			content: first.content,
-			style,
-			extra: self.extra,
+	merge_siblings(&mut node.extra);
+
+	// A first child with no style of its own can provide the content of its parent.
+	match (&mut node.content, node.extra.first()) {
+		(Content::Empty, Some(first)) if first.style.is_blank() => {
+			let first = node.extra.remove(0);
+			node.content = first.content;
+			node.extra.splice(0..0, first.extra);
+		}
+		(Content::Text(text), Some(first)) if first.style.is_blank() && first.extra.is_empty() => {
+			if let Content::Text(next) = &first.content {
+				text.push_str(next);
+				node.extra.remove(0);
+			}
 		}
+		_ => {}
 	}
+
+	// An empty node with a single child can absorb it completely.
+	if let (Content::Empty, 1) = (&node.content, node.extra.len()) {
+		let child = node.extra.pop().expect("node has one child");
+		node.style.merge(&child.style);
+		node.content = child.content;
+		node.extra = child.extra;
+	}
+
+	node
+}
+
+/// Compacts the translation arguments in a node's content.
+fn compact_nested(content: Content) -> Content {
+	match content {
+		Content::Translation { key, with } => Content::Translation {
+			key,
+			with: with.into_iter().map(Component::compact).collect(),
+		},
+		content => content,
+	}
+}
+
+/// Compacts the hover text in a style.
+fn compact_hover(mut style: Style) -> Style {
+	match &mut style.hover_event {
+		Some(HoverEvent::ShowText(text)) => {
+			**text = mem::take(&mut **text).compact();
+		}
+		Some(HoverEvent::ShowEntity(entity)) => {
+			entity.name = mem::take(&mut entity.name).compact();
+		}
+		_ => {}
+	}
+
+	style
+}
+
+/// Merges adjacent text nodes without children that have the same style.
+fn merge_siblings(children: &mut Vec<Component>) {
+	let mut merged: Vec<Component> = Vec::with_capacity(children.len());
+
+	for child in children.drain(..) {
+		if let Some(last) = merged.last_mut() {
+			if let (Content::Text(text), Content::Text(next)) = (&mut last.content, &child.content)
+			{
+				if last.extra.is_empty() && child.extra.is_empty() && last.style == child.style {
+					text.push_str(next);
+					continue;
+				}
+			}
+		}
+
+		merged.push(child);
+	}
+
+	*children = merged;
 }
 
 impl PartialEq<String> for Component {
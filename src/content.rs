@@ -1,3 +1,4 @@
+use crate::render::VirtualContent;
 use crate::Component;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
@@ -6,7 +7,7 @@ use std::fmt::{Display, Formatter};
 /// with each variant containing its own specialized fields.
 ///
 /// This enum should not be manually constructed. Use the factory functions in [Component] instead.
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 #[serde(rename_all = "snake_case")]
 pub enum Content {
@@ -43,6 +44,18 @@ pub enum Content {
 		value: String,
 	},
 
+	/// Content that is computed separately for each viewer when the component is rendered. Virtual
+	/// content holds a user-supplied [renderer][crate::render::VirtualComponent] and is replaced
+	/// with concrete nodes by a [VirtualRenderer][crate::render::VirtualRenderer].
+	///
+	/// Virtual content cannot be deserialized. When a component holding virtual content is
+	/// serialized without being rendered, the content is replaced with its fallback.
+	///
+	/// # Usage
+	/// To create a new virtual component, use [Component::deferred()].
+	#[serde(skip)]
+	Virtual(VirtualContent),
+
 	/// Localized component content. Translation components have a translation key and an array of
 	/// items that are interpolated into the translated message. Expected parameters vary based on
 	/// the translation key. The inner components can also be translation components.
@@ -82,6 +95,11 @@ impl Display for Content {
 				write!(f, ">")?;
 			}
 			Self::Empty => {}
+			Self::Virtual(content) => {
+				for node in content.fallback().into_iter().flat_map(Component::iter) {
+					write!(f, "{}", node.content)?;
+				}
+			}
 		}
 
 		Ok(())
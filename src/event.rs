@@ -2,6 +2,7 @@ use crate::{Component, Key};
 #[cfg(feature = "nbt")]
 use quartz_nbt::NbtCompound;
 use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
 use uuid::Uuid;
 
 /// Represents a click event. Click events are actions that are triggered when a user clicks on a
@@ -10,7 +11,7 @@ use uuid::Uuid;
 ///
 /// Since this enum and its variants are non-exhaustive, they must be constructed through the factory
 /// functions.
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[serde(tag = "action", content = "value", rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum ClickEvent {
@@ -75,7 +76,7 @@ impl ClickEvent {
 ///
 /// Since this enum and its variants are non-exhaustive, they must be constructed through the factory
 /// functions.
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[serde(tag = "action", content = "contents", rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum HoverEvent {
@@ -119,7 +120,7 @@ impl HoverEvent {
 
 /// A type modeling the data for showing [entity hovers][HoverEvent::show_entity()]. Entities do not
 /// need to exist for the client to render them.
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub struct EntityHover {
 	/// The entity's [id][Uuid].
@@ -199,6 +200,22 @@ impl ItemHover {
 // change in the future, the version for `quartz_nbt` has been pinned.
 impl Eq for ItemHover {}
 
+impl Hash for ItemHover {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.id.hash(state);
+		self.count.hash(state);
+
+		// NBT values include floats, which can't be hashed consistently with Eq, so only the tag's
+		// keys are hashed. They are sorted because compounds compare equal regardless of order.
+		#[cfg(feature = "nbt")]
+		if let Some(tag) = &self.tag {
+			let mut keys: Vec<_> = tag.inner().keys().collect();
+			keys.sort_unstable();
+			keys.hash(state);
+		}
+	}
+}
+
 #[cfg(feature = "nbt")]
 mod nbt {
 	use quartz_nbt::{snbt, NbtCompound};
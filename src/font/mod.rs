@@ -0,0 +1,254 @@
+//! A module for measuring the rendered width of components, in pixels. Widths are needed to center
+//! chat lines, pad menus, and fit text on signs.
+//!
+//! Glyph widths are looked up with a [FontMetrics] provider, keyed by the font of each node. The
+//! crate ships with [DefaultFontMetrics], which knows the advance widths of the ASCII characters in
+//! the vanilla default font. Custom fonts can be measured by implementing [FontMetrics] and
+//! chaining the provider in front of the default one with [FontMetrics::or()]. With the
+//! `resource-pack` crate feature, custom fonts can be loaded from a resource pack with
+//! `ResourcePackMetrics`.
+//!
+//! Components can also be split into lines that fit a given width with [Component::wrap()].
+//!
+//! # Examples
+//! ```
+//! use typewheel::Component;
+//! use typewheel::font::DefaultFontMetrics;
+//!
+//! let component = Component::text("Hi ").with_extra([Component::text("!").with_bold(true)]);
+//!
+//! // 'H' is 6 pixels wide, 'i' is 2, the space is 4, and a bold '!' is 2 + 1.
+//! assert_eq!(component.width(&DefaultFontMetrics), 15.0);
+//! ```
+
+#[cfg(feature = "resource-pack")]
+mod pack;
+mod wrap;
+
+#[cfg(feature = "resource-pack")]
+pub use self::pack::{FontLoadError, ResourcePackMetrics};
+use crate::iter::StyledNode;
+use crate::{Component, Content, Key};
+
+/// The width of the vanilla chat box, in pixels, at the default chat width setting.
+pub const CHAT_WIDTH: f32 = 320.0;
+
+/// The advance used for characters that no metrics provider knows. This is the width of most
+/// characters in the default font.
+const FALLBACK_ADVANCE: f32 = 6.0;
+
+/// The measurements of a single character in a font.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Glyph {
+	/// The horizontal distance the cursor moves after this glyph is drawn, in pixels. This includes
+	/// the spacing between characters.
+	pub advance: f32,
+
+	/// The extra advance added when this glyph is rendered in bold. Bold text is drawn twice, with
+	/// the second copy shifted by this amount.
+	pub bold_offset: f32,
+}
+
+impl Glyph {
+	/// Creates a glyph with the standard bold offset of 1 pixel.
+	#[inline]
+	pub const fn new(advance: f32) -> Self {
+		Self {
+			advance,
+			bold_offset: 1.0,
+		}
+	}
+
+	/// Creates a glyph that has no bold offset, such as glyphs from space fonts.
+	#[inline]
+	pub const fn space(advance: f32) -> Self {
+		Self {
+			advance,
+			bold_offset: 0.0,
+		}
+	}
+
+	/// Gets the advance of this glyph, including the bold offset if `bold` is set.
+	#[inline]
+	pub fn advance(&self, bold: bool) -> f32 {
+		if bold {
+			self.advance + self.bold_offset
+		} else {
+			self.advance
+		}
+	}
+}
+
+/// A provider of glyph measurements, keyed by font. Providers return [None] for fonts or
+/// characters they don't know, in which case measurement falls back to the [DefaultFontMetrics]
+/// and finally to a 6 pixel advance.
+///
+/// The [obfuscated][crate::Style::obfuscated] style does not affect widths, since the client only
+/// swaps glyphs for ones of the same width.
+pub trait FontMetrics {
+	/// Gets the measurements for a character in the provided font.
+	fn glyph(&self, font: &Key, c: char) -> Option<Glyph>;
+
+	/// Chains this provider with another one. The other provider is only queried for glyphs that
+	/// this provider does not know.
+	fn or<M: FontMetrics>(self, other: M) -> Or<Self, M>
+	where
+		Self: Sized,
+	{
+		Or(self, other)
+	}
+}
+
+impl<M: FontMetrics + ?Sized> FontMetrics for &M {
+	#[inline(always)]
+	fn glyph(&self, font: &Key, c: char) -> Option<Glyph> {
+		(**self).glyph(font, c)
+	}
+}
+
+impl<M: FontMetrics + ?Sized> FontMetrics for Box<M> {
+	#[inline(always)]
+	fn glyph(&self, font: &Key, c: char) -> Option<Glyph> {
+		(**self).glyph(font, c)
+	}
+}
+
+/// A [FontMetrics] provider that tries two providers in order. See [FontMetrics::or()].
+#[derive(Clone, Debug)]
+pub struct Or<A, B>(A, B);
+
+impl<A: FontMetrics, B: FontMetrics> FontMetrics for Or<A, B> {
+	fn glyph(&self, font: &Key, c: char) -> Option<Glyph> {
+		self.0.glyph(font, c).or_else(|| self.1.glyph(font, c))
+	}
+}
+
+/// Metrics for the printable ASCII characters of the vanilla default font (`minecraft:default`).
+/// Other fonts and characters are not known by this provider.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultFontMetrics;
+
+impl DefaultFontMetrics {
+	/// The key of the vanilla default font.
+	pub fn font() -> Key {
+		Key::minecraft("default")
+	}
+
+	/// Gets the advance of an ASCII character in the default font, without bold.
+	const fn ascii_advance(c: char) -> Option<f32> {
+		let advance = match c {
+			'!' | '\'' | ',' | '.' | ':' | ';' | 'i' | '|' => 2.0,
+			'`' | 'l' => 3.0,
+			' ' | '"' | 'I' | '[' | ']' | 't' => 4.0,
+			'(' | ')' | '*' | '<' | '>' | 'f' | 'k' | '{' | '}' => 5.0,
+			'@' | '~' => 7.0,
+			' '..='~' => 6.0,
+			_ => return None,
+		};
+
+		Some(advance)
+	}
+}
+
+impl FontMetrics for DefaultFontMetrics {
+	fn glyph(&self, font: &Key, c: char) -> Option<Glyph> {
+		if font.parts() != (Key::MINECRAFT_NAMESPACE, "default") {
+			return None;
+		}
+
+		Self::ascii_advance(c).map(Glyph::new)
+	}
+}
+
+/// Metrics where every character is exactly one unit wide, regardless of font or boldness. This
+/// is useful for measuring and [wrapping][Component::wrap()] text by character count.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Monospace;
+
+impl FontMetrics for Monospace {
+	#[inline(always)]
+	fn glyph(&self, _font: &Key, _c: char) -> Option<Glyph> {
+		Some(Glyph::space(1.0))
+	}
+}
+
+/// Gets the advance of a character, falling back to the default font if the provider doesn't
+/// know it.
+fn advance(metrics: &impl FontMetrics, font: &Key, c: char, bold: bool) -> f32 {
+	metrics
+		.glyph(font, c)
+		.or_else(|| DefaultFontMetrics::ascii_advance(c).map(Glyph::new))
+		.unwrap_or(Glyph::new(FALLBACK_ADVANCE))
+		.advance(bold)
+}
+
+impl Component {
+	/// Measures the width of this component in pixels, as it would be rendered on a single line.
+	///
+	/// Each node is measured with its effective font and boldness. Only [text][Content::Text] and
+	/// [score][Content::Score] content is measured; content that is resolved by the client, like
+	/// translations and keybinds, has an unknown width and is not counted. Such content should be
+	/// [rendered][crate::render] into text first.
+	pub fn width(&self, metrics: &impl FontMetrics) -> f32 {
+		let default_font = DefaultFontMetrics::font();
+		let mut width = 0.0;
+
+		for StyledNode { node, style, .. } in self.iter_styled() {
+			let text = match &node.content {
+				Content::Text(text) => text.as_str(),
+				Content::Score { value, .. } => value.as_str(),
+				_ => continue,
+			};
+
+			let font = style.font.as_deref().map(Key::from);
+			let font = font.as_ref().unwrap_or(&default_font);
+			let bold = style.bold.unwrap_or(false);
+
+			width += text
+				.chars()
+				.map(|c| advance(metrics, font, c, bold))
+				.sum::<f32>();
+		}
+
+		width
+	}
+
+	/// Pads this component with spaces on the left so that it is centered in a line of the
+	/// provided width. If the component is already wider than the line, it is returned unchanged.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::Component;
+	/// use typewheel::font::{DefaultFontMetrics, CHAT_WIDTH};
+	///
+	/// let centered = Component::text("Welcome!").centered(CHAT_WIDTH, &DefaultFontMetrics);
+	/// assert_eq!(centered.extra[0], Component::text(" ".repeat(34)));
+	/// ```
+	#[must_use]
+	pub fn centered(self, width: f32, metrics: &impl FontMetrics) -> Self {
+		let padding = (width - self.width(metrics)) / 2.0;
+		self.padded(padding, metrics)
+	}
+
+	/// Pads this component with spaces on the left so that it is aligned to the right edge of a
+	/// line of the provided width. If the component is already wider than the line, it is returned
+	/// unchanged.
+	#[must_use]
+	pub fn aligned_right(self, width: f32, metrics: &impl FontMetrics) -> Self {
+		let padding = width - self.width(metrics);
+		self.padded(padding, metrics)
+	}
+
+	/// Prepends as many unstyled spaces as fit in `padding` pixels.
+	fn padded(self, padding: f32, metrics: &impl FontMetrics) -> Self {
+		let space = advance(metrics, &DefaultFontMetrics::font(), ' ', false);
+		let count = (padding / space).floor();
+
+		if count < 1.0 {
+			return self;
+		}
+
+		Component::empty().with_extra([Component::text(" ".repeat(count as usize)), self])
+	}
+}
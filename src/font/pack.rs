@@ -0,0 +1,340 @@
+use super::{FontMetrics, Glyph};
+use crate::Key;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+/// The maximum number of `reference` providers followed when looking up a glyph. This guards
+/// against fonts that reference each other in a loop.
+const MAX_REFERENCE_DEPTH: usize = 8;
+
+/// [FontMetrics] loaded from the font definitions of a resource pack. This is locked behind the
+/// `resource-pack` crate feature.
+///
+/// Fonts are read from `assets/<namespace>/font/<path>.json` files, and are keyed by the font
+/// [Key] `<namespace>:<path>` that [Style::font][crate::Style::font] refers to. The following
+/// provider types are supported:
+/// * `bitmap`: Glyph widths are computed from the texture the same way the client does it, by
+///   finding the rightmost column of each glyph cell with a visible pixel.
+/// * `space`: Glyphs with fixed advances and no bold offset.
+/// * `reference`: Includes all of the providers of another font.
+///
+/// Other provider types (such as `ttf` and `unihex`) are skipped, so their glyphs fall back to the
+/// default metrics when measuring.
+///
+/// # Examples
+/// ```no_run
+/// use typewheel::Component;
+/// use typewheel::font::{DefaultFontMetrics, FontMetrics, ResourcePackMetrics};
+///
+/// let pack = ResourcePackMetrics::load_dir("resourcepack").unwrap();
+/// let metrics = pack.or(DefaultFontMetrics);
+///
+/// let component = Component::text("\u{E000}").with_font("custom:icons");
+/// println!("{}", component.width(&metrics));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ResourcePackMetrics {
+	fonts: HashMap<Key, Vec<Provider>>,
+}
+
+#[derive(Clone, Debug)]
+enum Provider {
+	Glyphs(HashMap<char, Glyph>),
+	Reference(Key),
+}
+
+impl ResourcePackMetrics {
+	/// Creates an empty set of metrics with no fonts.
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Loads every font definition in an unpacked resource pack directory. See [Self::add_dir()].
+	pub fn load_dir(root: impl AsRef<Path>) -> Result<Self, FontLoadError> {
+		let mut metrics = Self::new();
+		metrics.add_dir(root)?;
+		Ok(metrics)
+	}
+
+	/// Loads every font definition in an unpacked resource pack directory. The directory should
+	/// contain the pack's `assets` folder.
+	///
+	/// Packs can be layered by calling this method for each one, from the lowest priority pack to
+	/// the highest. Fonts that are defined in several packs take glyphs from the packs loaded last
+	/// first.
+	pub fn add_dir(&mut self, root: impl AsRef<Path>) -> Result<(), FontLoadError> {
+		let assets = root.as_ref().join("assets");
+
+		for namespace in fs::read_dir(&assets)? {
+			let namespace = namespace?;
+			if !namespace.file_type()?.is_dir() {
+				continue;
+			}
+
+			let namespace_dir = namespace.path();
+			let namespace = namespace.file_name().to_string_lossy().into_owned();
+			let font_dir = namespace_dir.join("font");
+			if !font_dir.is_dir() {
+				continue;
+			}
+
+			for (path, file) in font_files(&font_dir)? {
+				let definition = fs::read(&file)?;
+				self.add_font(Key::new(&namespace, path), &definition, |texture| {
+					let (namespace, path) = texture.parts();
+					fs::read(assets.join(namespace).join("textures").join(path))
+				})?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Adds a single font from the contents of its JSON definition. Textures referenced by bitmap
+	/// providers are read with the provided function, which receives the texture key as written
+	/// in the definition (such as `minecraft:font/ascii.png`).
+	///
+	/// If the font already exists, the new providers take precedence over the existing ones.
+	pub fn add_font(
+		&mut self,
+		font: Key,
+		definition: &[u8],
+		mut read_texture: impl FnMut(&Key) -> io::Result<Vec<u8>>,
+	) -> Result<(), FontLoadError> {
+		let definition: FontDefinition = serde_json::from_slice(definition)?;
+		let mut providers = Vec::with_capacity(definition.providers.len());
+
+		for provider in definition.providers {
+			providers.push(match provider {
+				ProviderDefinition::Bitmap {
+					file,
+					height,
+					chars,
+				} => {
+					let texture = read_texture(&file)?;
+					Provider::Glyphs(bitmap_glyphs(&texture, height, &chars)?)
+				}
+				ProviderDefinition::Space { advances } => Provider::Glyphs(
+					advances
+						.into_iter()
+						.map(|(c, advance)| (c, Glyph::space(advance)))
+						.collect(),
+				),
+				ProviderDefinition::Reference { id } => Provider::Reference(id),
+				ProviderDefinition::Unsupported => continue,
+			});
+		}
+
+		let existing = self.fonts.entry(font).or_default();
+		providers.append(existing);
+		*existing = providers;
+
+		Ok(())
+	}
+
+	/// Checks if a font has been loaded.
+	pub fn contains_font(&self, font: &Key) -> bool {
+		self.fonts.contains_key(font)
+	}
+
+	fn lookup(&self, font: &Key, c: char, depth: usize) -> Option<Glyph> {
+		if depth > MAX_REFERENCE_DEPTH {
+			return None;
+		}
+
+		self.fonts
+			.get(font)?
+			.iter()
+			.find_map(|provider| match provider {
+				Provider::Glyphs(glyphs) => glyphs.get(&c).copied(),
+				Provider::Reference(id) => self.lookup(id, c, depth + 1),
+			})
+	}
+}
+
+impl FontMetrics for ResourcePackMetrics {
+	#[inline]
+	fn glyph(&self, font: &Key, c: char) -> Option<Glyph> {
+		self.lookup(font, c, 0)
+	}
+}
+
+/// Lists the font definitions in a `font` directory, returning each font's path (without the
+/// `.json` extension) and its file.
+fn font_files(dir: &Path) -> io::Result<Vec<(String, PathBuf)>> {
+	let mut out = Vec::new();
+	let mut pending = vec![(String::new(), dir.to_path_buf())];
+
+	while let Some((prefix, dir)) = pending.pop() {
+		for entry in fs::read_dir(dir)? {
+			let entry = entry?;
+			let name = entry.file_name().to_string_lossy().into_owned();
+
+			if entry.file_type()?.is_dir() {
+				pending.push((format!("{prefix}{name}/"), entry.path()));
+			} else if let Some(stem) = name.strip_suffix(".json") {
+				out.push((format!("{prefix}{stem}"), entry.path()));
+			}
+		}
+	}
+
+	Ok(out)
+}
+
+/// Computes glyph advances for a bitmap provider. The texture is split into a grid with one cell
+/// per character, and each glyph's width is the rightmost column of its cell with a pixel that
+/// isn't fully transparent. This mirrors the client's bitmap font loader.
+fn bitmap_glyphs(
+	texture: &[u8],
+	height: f32,
+	rows: &[String],
+) -> Result<HashMap<char, Glyph>, FontLoadError> {
+	let mut decoder = png::Decoder::new(Cursor::new(texture));
+	decoder.set_transformations(
+		png::Transformations::normalize_to_color8() | png::Transformations::ALPHA,
+	);
+
+	let mut reader = decoder.read_info()?;
+	let mut pixels = vec![0; reader.output_buffer_size()];
+	let info = reader.next_frame(&mut pixels)?;
+
+	let rows: Vec<Vec<char>> = rows.iter().map(|row| row.chars().collect()).collect();
+	let columns = rows.first().map_or(0, Vec::len);
+	if columns == 0 || rows.iter().any(|row| row.len() != columns) {
+		return Err(FontLoadError::InvalidFont(
+			"bitmap rows must all have the same, non-zero length".into(),
+		));
+	}
+
+	let cell_width = info.width as usize / columns;
+	let cell_height = info.height as usize / rows.len();
+	if cell_width == 0 || cell_height == 0 {
+		return Err(FontLoadError::InvalidFont(
+			"bitmap texture is too small for its rows".into(),
+		));
+	}
+
+	let samples = info.color_type.samples();
+	let scale = height / cell_height as f32;
+
+	let is_visible = |x: usize, y: usize| {
+		// The alpha channel is always the last sample.
+		pixels[y * info.line_size + x * samples + samples - 1] != 0
+	};
+
+	let mut glyphs = HashMap::new();
+	for (row, chars) in rows.iter().enumerate() {
+		for (column, &c) in chars.iter().enumerate() {
+			// Null characters mark unused cells.
+			if c == '\0' {
+				continue;
+			}
+
+			let (left, top) = (column * cell_width, row * cell_height);
+			let width = (0..cell_width)
+				.rev()
+				.find(|x| (top..top + cell_height).any(|y| is_visible(left + x, y)))
+				.map_or(0, |x| x + 1);
+
+			let advance = (0.5 + width as f32 * scale).floor() + 1.0;
+			glyphs.insert(c, Glyph::new(advance));
+		}
+	}
+
+	Ok(glyphs)
+}
+
+#[derive(Deserialize)]
+struct FontDefinition {
+	providers: Vec<ProviderDefinition>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ProviderDefinition {
+	Bitmap {
+		file: Key,
+		#[serde(default = "default_height")]
+		height: f32,
+		chars: Vec<String>,
+	},
+	Space {
+		advances: HashMap<char, f32>,
+	},
+	Reference {
+		id: Key,
+	},
+	#[serde(other)]
+	Unsupported,
+}
+
+/// The default `height` of a bitmap provider.
+#[inline(always)]
+fn default_height() -> f32 {
+	8.0
+}
+
+/// An error that occurred while loading font metrics from a resource pack.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FontLoadError {
+	/// A file could not be read.
+	Io(io::Error),
+
+	/// A font definition is not valid JSON, or does not match the font definition format.
+	Json(serde_json::Error),
+
+	/// A bitmap texture could not be decoded.
+	Png(png::DecodingError),
+
+	/// A font definition is well-formed but describes an invalid font.
+	InvalidFont(String),
+}
+
+impl Display for FontLoadError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Io(error) => write!(f, "failed to read font file: {error}"),
+			Self::Json(error) => write!(f, "invalid font definition: {error}"),
+			Self::Png(error) => write!(f, "invalid font texture: {error}"),
+			Self::InvalidFont(message) => write!(f, "invalid font: {message}"),
+		}
+	}
+}
+
+impl std::error::Error for FontLoadError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Io(error) => Some(error),
+			Self::Json(error) => Some(error),
+			Self::Png(error) => Some(error),
+			Self::InvalidFont(_) => None,
+		}
+	}
+}
+
+impl From<io::Error> for FontLoadError {
+	#[inline(always)]
+	fn from(value: io::Error) -> Self {
+		Self::Io(value)
+	}
+}
+
+impl From<serde_json::Error> for FontLoadError {
+	#[inline(always)]
+	fn from(value: serde_json::Error) -> Self {
+		Self::Json(value)
+	}
+}
+
+impl From<png::DecodingError> for FontLoadError {
+	#[inline(always)]
+	fn from(value: png::DecodingError) -> Self {
+		Self::Png(value)
+	}
+}
@@ -0,0 +1,222 @@
+use super::{advance, DefaultFontMetrics, FontMetrics};
+use crate::{Component, Content, Key, Style};
+
+/// A piece of a component with its effective style. Text is split into characters when laying
+/// out lines, while other content is kept whole.
+enum Run {
+	Text(Style, String),
+	Atom(Component),
+}
+
+/// A single character or atom in a line, pointing back to the run it came from.
+#[derive(Clone, Copy)]
+struct Unit {
+	run: usize,
+	ch: Option<char>,
+	width: f32,
+}
+
+impl Component {
+	/// Splits this component into lines at explicit line breaks (`\n`). Each line is a new
+	/// component where every piece of text carries its full effective style, including click and
+	/// hover events, so lines can be displayed independently of each other.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::{Component, TextColor};
+	///
+	/// let component = Component::text("first\nsec")
+	///     .with_color(TextColor::Red)
+	///     .with_extra(["ond"]);
+	///
+	/// assert_eq!(
+	///     component.lines(),
+	///     [
+	///         Component::text("first").with_color(TextColor::Red),
+	///         Component::text("second").with_color(TextColor::Red),
+	///     ]
+	/// );
+	/// ```
+	pub fn lines(&self) -> Vec<Component> {
+		self.layout(f32::INFINITY, &DefaultFontMetrics)
+	}
+
+	/// Splits this component into lines that fit within the provided width, as measured by a
+	/// [FontMetrics] provider. Lines are broken at spaces where possible, and words that are wider
+	/// than a whole line are broken wherever they overflow. The spaces that lines are broken at are
+	/// removed. Explicit line breaks (`\n`) are kept as well; see [Self::lines()].
+	///
+	/// To wrap at a number of characters instead of pixels, use the [Monospace][super::Monospace]
+	/// metrics.
+	///
+	/// Content other than text is never split. Like with [Self::width()], only score values have a
+	/// known width.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::{Component, TextColor};
+	/// use typewheel::font::Monospace;
+	///
+	/// let component = Component::text("the quick ")
+	///     .with_extra([Component::text("brown fox").with_color(TextColor::Gold)]);
+	///
+	/// assert_eq!(
+	///     component.wrap(10.0, &Monospace),
+	///     [
+	///         Component::text("the quick"),
+	///         Component::text("brown fox").with_color(TextColor::Gold),
+	///     ]
+	/// );
+	/// ```
+	pub fn wrap(&self, width: f32, metrics: &impl FontMetrics) -> Vec<Component> {
+		self.layout(width, metrics)
+	}
+
+	fn layout(&self, max_width: f32, metrics: &impl FontMetrics) -> Vec<Component> {
+		let runs = self.effective_runs();
+		let default_font = DefaultFontMetrics::font();
+
+		let mut lines = Vec::new();
+		let mut units = Vec::new();
+
+		for (index, run) in runs.iter().enumerate() {
+			match run {
+				Run::Text(style, text) => {
+					let font = style.font.as_deref().map(Key::from);
+					let font = font.as_ref().unwrap_or(&default_font);
+					let bold = style.bold.unwrap_or(false);
+
+					for ch in text.chars() {
+						if ch == '\n' {
+							break_lines(&runs, &mut units, max_width, &mut lines);
+							continue;
+						}
+
+						units.push(Unit {
+							run: index,
+							ch: Some(ch),
+							width: advance(metrics, font, ch, bold),
+						});
+					}
+				}
+				Run::Atom(atom) => units.push(Unit {
+					run: index,
+					ch: None,
+					width: atom.width(metrics),
+				}),
+			}
+		}
+
+		break_lines(&runs, &mut units, max_width, &mut lines);
+		lines
+	}
+
+	/// Flattens this component into runs of content with their effective style.
+	fn effective_runs(&self) -> Vec<Run> {
+		self.to_runs()
+			.into_iter()
+			.map(|run| match run.content {
+				Content::Text(text) => Run::Text(run.style, text),
+				content => Run::Atom(Component::create_flat(content, run.style)),
+			})
+			.collect()
+	}
+}
+
+/// Breaks a single paragraph of units into lines that fit in `max_width`, and appends them to
+/// `lines`. The units are drained in the process.
+fn break_lines(runs: &[Run], units: &mut Vec<Unit>, max_width: f32, lines: &mut Vec<Component>) {
+	let mut start = 0;
+	let mut width = 0.0;
+	let mut last_space = None;
+	let mut i = 0;
+
+	while i < units.len() {
+		let unit = units[i];
+		let is_space = unit.ch == Some(' ');
+
+		if width + unit.width > max_width && i > start {
+			if is_space {
+				// Break at this space, dropping it.
+				lines.push(build_line(runs, &units[start..i]));
+				start = i + 1;
+				i += 1;
+				width = 0.0;
+				last_space = None;
+				continue;
+			}
+
+			match last_space.take() {
+				Some(space) => {
+					lines.push(build_line(runs, &units[start..space]));
+					start = space + 1;
+					width = units[start..i].iter().map(|unit| unit.width).sum();
+				}
+				None => {
+					// The word doesn't fit on a line of its own, so it has to be split.
+					lines.push(build_line(runs, &units[start..i]));
+					start = i;
+					width = 0.0;
+				}
+			}
+
+			// Check the same unit again against the new line.
+			continue;
+		}
+
+		if is_space {
+			last_space = Some(i);
+		}
+
+		width += unit.width;
+		i += 1;
+	}
+
+	lines.push(build_line(runs, &units[start..]));
+	units.clear();
+}
+
+/// Builds a line from a slice of units, merging consecutive characters with the same style.
+fn build_line(runs: &[Run], units: &[Unit]) -> Component {
+	let mut pieces: Vec<Component> = Vec::new();
+	let mut current: Option<(usize, String)> = None;
+
+	let flush = |current: &mut Option<(usize, String)>, pieces: &mut Vec<Component>| {
+		if let Some((run, text)) = current.take() {
+			if let Run::Text(style, _) = &runs[run] {
+				pieces.push(Component::create_flat(Content::Text(text), style.clone()));
+			}
+		}
+	};
+
+	for unit in units {
+		match (unit.ch, &mut current) {
+			(Some(ch), Some((run, text))) if same_style(&runs[*run], &runs[unit.run]) => {
+				text.push(ch)
+			}
+			(Some(ch), _) => {
+				flush(&mut current, &mut pieces);
+				current = Some((unit.run, ch.to_string()));
+			}
+			(None, _) => {
+				flush(&mut current, &mut pieces);
+				if let Run::Atom(atom) = &runs[unit.run] {
+					pieces.push(atom.clone());
+				}
+			}
+		}
+	}
+
+	flush(&mut current, &mut pieces);
+
+	if pieces.len() == 1 {
+		pieces.pop().expect("one piece is present")
+	} else {
+		Component::empty().with_extra(pieces)
+	}
+}
+
+/// Checks if two runs are both text with the same style.
+fn same_style(a: &Run, b: &Run) -> bool {
+	matches!((a, b), (Run::Text(a, _), Run::Text(b, _)) if a == b)
+}
@@ -0,0 +1,694 @@
+//! Support code for the [format_component!][crate::format_component!] macro. Nothing in this
+//! module is meant to be used directly.
+//!
+//! Format strings are parsed entirely in a `const` context, where any error becomes a compile error.
+//! The macro stores the result as a table of [Piece]s, so building a component at runtime only
+//! slices out literal text and inserts arguments.
+
+use crate::{Component, Style, TextColor};
+use std::fmt::Display;
+
+/// A wrapper used to pick how a macro argument is turned into a [Component]. Arguments that
+/// implement [`Into<Component>`][Into] are converted directly, and anything else that implements
+/// [Display] is formatted into a text component.
+pub struct Arg<T>(pub T);
+
+/// Converts an argument with [`Into<Component>`][Into]. This takes priority over [ViaDisplay].
+pub trait ViaComponent {
+	fn __into_component(self) -> Component;
+}
+
+impl<T: Into<Component>> ViaComponent for Arg<T> {
+	#[inline(always)]
+	fn __into_component(self) -> Component {
+		self.0.into()
+	}
+}
+
+/// Converts an argument with [Display]. Since this is implemented on a reference, method
+/// resolution only falls back to it if [ViaComponent] doesn't apply.
+pub trait ViaDisplay {
+	fn __into_component(self) -> Component;
+}
+
+impl<T: Display> ViaDisplay for &Arg<T> {
+	#[inline(always)]
+	fn __into_component(self) -> Component {
+		Component::text(self.0.to_string())
+	}
+}
+
+/// A piece of a format string.
+#[derive(Clone, Copy)]
+enum Segment {
+	/// The end of the string.
+	End,
+
+	/// A run of literal text in `start..end`.
+	Literal { start: usize, end: usize },
+
+	/// An escaped brace (`{{` or `}}`), which stands for the brace at `at`.
+	Escaped { at: usize },
+
+	/// A slot. The name is in `name.0..name.1` (and is empty for positional slots), and the style
+	/// spec is in `spec.0..spec.1`.
+	Slot {
+		name: (usize, usize),
+		spec: (usize, usize),
+	},
+
+	/// A syntax error.
+	Error(&'static str),
+}
+
+/// Reads the segment starting at `pos`, returning it along with the position of the next one.
+const fn next_segment(fmt: &[u8], pos: usize) -> (Segment, usize) {
+	if pos >= fmt.len() {
+		return (Segment::End, pos);
+	}
+
+	match fmt[pos] {
+		b'{' if pos + 1 < fmt.len() && fmt[pos + 1] == b'{' => {
+			(Segment::Escaped { at: pos }, pos + 2)
+		}
+		b'}' if pos + 1 < fmt.len() && fmt[pos + 1] == b'}' => {
+			(Segment::Escaped { at: pos }, pos + 2)
+		}
+		b'}' => (Segment::Error("unmatched `}` in format string"), pos),
+		b'{' => {
+			let mut end = pos + 1;
+			let mut colon = 0;
+			while end < fmt.len() && fmt[end] != b'}' {
+				if fmt[end] == b'{' {
+					return (Segment::Error("unexpected `{` in format slot"), pos);
+				}
+				if fmt[end] == b':' && colon == 0 {
+					colon = end;
+				}
+				end += 1;
+			}
+
+			if end >= fmt.len() {
+				return (Segment::Error("unmatched `{` in format string"), pos);
+			}
+
+			let (name, spec) = if colon == 0 {
+				((pos + 1, end), (end, end))
+			} else {
+				((pos + 1, colon), (colon + 1, end))
+			};
+
+			if !is_identifier(fmt, name.0, name.1) {
+				return (Segment::Error("slot names must be identifiers"), pos);
+			}
+
+			(Segment::Slot { name, spec }, end + 1)
+		}
+		_ => {
+			let mut end = pos;
+			while end < fmt.len() && fmt[end] != b'{' && fmt[end] != b'}' {
+				end += 1;
+			}
+
+			(Segment::Literal { start: pos, end }, end)
+		}
+	}
+}
+
+/// Checks if `start..end` is empty or a valid identifier.
+const fn is_identifier(fmt: &[u8], start: usize, end: usize) -> bool {
+	let mut i = start;
+	while i < end {
+		let c = fmt[i];
+		let valid = c == b'_' || c.is_ascii_alphabetic() || (i > start && c.is_ascii_digit());
+		if !valid {
+			return false;
+		}
+		i += 1;
+	}
+
+	true
+}
+
+/// Checks if `start..end` holds exactly `word`.
+const fn eq_range(fmt: &[u8], start: usize, end: usize, word: &str) -> bool {
+	let word = word.as_bytes();
+	if end - start != word.len() {
+		return false;
+	}
+
+	let mut i = 0;
+	while i < word.len() {
+		if fmt[start + i] != word[i] {
+			return false;
+		}
+		i += 1;
+	}
+
+	true
+}
+
+const COLORS: [(&str, TextColor); 16] = [
+	("black", TextColor::Black),
+	("dark_blue", TextColor::DarkBlue),
+	("dark_green", TextColor::DarkGreen),
+	("dark_aqua", TextColor::DarkAqua),
+	("dark_red", TextColor::DarkRed),
+	("dark_purple", TextColor::DarkPurple),
+	("gold", TextColor::Gold),
+	("gray", TextColor::Gray),
+	("dark_gray", TextColor::DarkGray),
+	("blue", TextColor::Blue),
+	("green", TextColor::Green),
+	("aqua", TextColor::Aqua),
+	("red", TextColor::Red),
+	("light_purple", TextColor::LightPurple),
+	("yellow", TextColor::Yellow),
+	("white", TextColor::White),
+];
+
+const DECORATIONS: [&str; 5] = [
+	"bold",
+	"italic",
+	"underlined",
+	"strikethrough",
+	"obfuscated",
+];
+
+/// A single parsed entry of a style spec.
+#[derive(Clone, Copy)]
+enum StyleToken {
+	Color(TextColor),
+
+	/// A decoration from [DECORATIONS] by index, and whether it is enabled.
+	Decoration(usize, bool),
+}
+
+/// Parses a single style entry in `start..end`.
+const fn parse_style(fmt: &[u8], start: usize, end: usize) -> Option<StyleToken> {
+	if start < end && fmt[start] == b'#' {
+		if end - start != 7 {
+			return None;
+		}
+
+		let mut value = 0;
+		let mut i = start + 1;
+		while i < end {
+			let digit = match fmt[i] {
+				c @ b'0'..=b'9' => c - b'0',
+				c @ b'a'..=b'f' => c - b'a' + 10,
+				c @ b'A'..=b'F' => c - b'A' + 10,
+				_ => return None,
+			};
+			value = value * 16 + digit as u32;
+			i += 1;
+		}
+
+		return Some(StyleToken::Color(TextColor::Hex(value)));
+	}
+
+	let mut i = 0;
+	while i < COLORS.len() {
+		if eq_range(fmt, start, end, COLORS[i].0) {
+			return Some(StyleToken::Color(COLORS[i].1));
+		}
+		i += 1;
+	}
+
+	let (negated, start) = if start < end && fmt[start] == b'!' {
+		(true, start + 1)
+	} else {
+		(false, start)
+	};
+
+	let mut i = 0;
+	while i < DECORATIONS.len() {
+		if eq_range(fmt, start, end, DECORATIONS[i]) {
+			return Some(StyleToken::Decoration(i, !negated));
+		}
+		i += 1;
+	}
+
+	None
+}
+
+/// The styles from a slot's spec. Unlike a [Style], this can be built in a `const` context.
+#[derive(Clone, Copy)]
+pub struct Spec {
+	color: Option<TextColor>,
+	decorations: [Option<bool>; 5],
+}
+
+impl Spec {
+	/// Parses a validated style spec in `start..end`.
+	const fn parse(fmt: &[u8], start: usize, end: usize) -> Self {
+		let mut spec = Self {
+			color: None,
+			decorations: [None; 5],
+		};
+
+		if start == end {
+			return spec;
+		}
+
+		let mut entry = start;
+		loop {
+			let mut entry_end = entry;
+			while entry_end < end && fmt[entry_end] != b',' {
+				entry_end += 1;
+			}
+
+			match parse_style(fmt, entry, entry_end) {
+				Some(StyleToken::Color(color)) => spec.color = Some(color),
+				Some(StyleToken::Decoration(index, state)) => spec.decorations[index] = Some(state),
+				None => panic!("invalid style in format slot"),
+			}
+
+			if entry_end >= end {
+				return spec;
+			}
+			entry = entry_end + 1;
+		}
+	}
+
+	fn to_style(self) -> Style {
+		let [bold, italic, underlined, strikethrough, obfuscated] = self.decorations;
+
+		Style {
+			bold,
+			italic,
+			underlined,
+			strikethrough,
+			obfuscated,
+			color: self.color,
+			..Default::default()
+		}
+	}
+}
+
+/// A piece of a format string, precomputed at compile time.
+#[derive(Clone, Copy)]
+pub enum Piece {
+	/// Literal text in `start..end` of the format string's [unescaped text][text()].
+	Text { start: usize, end: usize },
+
+	/// An argument, by index. Positional arguments come first, followed by named ones in the order
+	/// that they were passed. `last` is set on the final slot that uses an argument, which can move
+	/// it instead of cloning it.
+	Arg {
+		index: usize,
+		spec: Spec,
+		last: bool,
+	},
+}
+
+impl Piece {
+	fn build(self, text: &str, args: &mut [Option<Component>]) -> Component {
+		match self {
+			Self::Text { start, end } => Component::text(&text[start..end]),
+			Self::Arg { index, spec, last } => {
+				let value = if last {
+					args[index].take()
+				} else {
+					args[index].clone()
+				};
+				let mut value = value.expect("arguments are only taken by their last slot");
+
+				// The slot's style acts like a parent's, so the argument's own style wins.
+				let mut style = spec.to_style();
+				style.merge(&value.style);
+				value.style = style;
+
+				value
+			}
+		}
+	}
+}
+
+/// Checks a format string against the macro's arguments, and returns the number of [Piece]s and
+/// the length of the [unescaped text][text()] it is made of. Errors panic; this is evaluated in a
+/// `const` item, so the panics surface as compile errors.
+///
+/// `late` is the number of positional arguments that were passed after named ones.
+pub const fn measure(fmt: &str, names: &[&str], positional: usize, late: usize) -> (usize, usize) {
+	if late > 0 {
+		panic!("positional arguments cannot follow named arguments");
+	}
+
+	let mut i = 0;
+	while i < names.len() {
+		let name = names[i].as_bytes();
+		let mut j = 0;
+		while j < i {
+			if eq_range(name, 0, name.len(), names[j]) {
+				panic!("duplicate named argument");
+			}
+			j += 1;
+		}
+		i += 1;
+	}
+
+	let fmt = fmt.as_bytes();
+	let mut pos = 0;
+	let mut slots = 0;
+	let mut pieces = 0;
+	let mut text = 0;
+	let mut in_text = false;
+	let mut used = [false; 64];
+
+	if names.len() > used.len() {
+		panic!("too many named arguments");
+	}
+
+	loop {
+		let (segment, next) = next_segment(fmt, pos);
+		match segment {
+			Segment::End => break,
+			Segment::Error(message) => panic!("{}", message),
+			Segment::Literal { start, end } => {
+				pieces += !in_text as usize;
+				in_text = true;
+				text += end - start;
+			}
+			Segment::Escaped { .. } => {
+				pieces += !in_text as usize;
+				in_text = true;
+				text += 1;
+			}
+			Segment::Slot { name, spec } => {
+				pieces += 1;
+				in_text = false;
+				Spec::parse(fmt, spec.0, spec.1);
+
+				if name.0 == name.1 {
+					slots += 1;
+				} else {
+					match find_name(fmt, name, names) {
+						Some(i) => used[i] = true,
+						None => {
+							panic!("format slot refers to a named argument that was not provided")
+						}
+					}
+				}
+			}
+		}
+		pos = next;
+	}
+
+	if slots != positional {
+		panic!("the number of `{{}}` slots does not match the number of positional arguments");
+	}
+
+	let mut i = 0;
+	while i < names.len() {
+		if !used[i] {
+			panic!("named argument is never used in the format string");
+		}
+		i += 1;
+	}
+
+	(pieces, text)
+}
+
+/// Finds the index of the named argument in `name.0..name.1`.
+const fn find_name(fmt: &[u8], name: (usize, usize), names: &[&str]) -> Option<usize> {
+	let mut i = 0;
+	while i < names.len() {
+		if eq_range(fmt, name.0, name.1, names[i]) {
+			return Some(i);
+		}
+		i += 1;
+	}
+
+	None
+}
+
+/// Collects the literal text of a [measured][measure()] format string, with escaped braces
+/// replaced by the braces they stand for.
+pub const fn text<const N: usize>(fmt: &str) -> [u8; N] {
+	let fmt = fmt.as_bytes();
+	let mut out = [0; N];
+	let mut len = 0;
+	let mut pos = 0;
+
+	loop {
+		let (segment, next) = next_segment(fmt, pos);
+		match segment {
+			Segment::End => return out,
+			Segment::Literal { start, end } => {
+				let mut i = start;
+				while i < end {
+					out[len] = fmt[i];
+					len += 1;
+					i += 1;
+				}
+			}
+			Segment::Escaped { at } => {
+				out[len] = fmt[at];
+				len += 1;
+			}
+			Segment::Slot { .. } | Segment::Error(_) => {}
+		}
+		pos = next;
+	}
+}
+
+/// Splits a [measured][measure()] format string into its pieces. Adjacent literals and escaped
+/// braces are merged into a single text piece.
+pub const fn pieces<const N: usize>(fmt: &str, names: &[&str], positional: usize) -> [Piece; N] {
+	let fmt = fmt.as_bytes();
+	let mut out = [Piece::Text { start: 0, end: 0 }; N];
+	let mut len = 0;
+	let mut text = 0;
+	let mut in_text = false;
+	let mut slots = 0;
+	let mut pos = 0;
+
+	loop {
+		let (segment, next) = next_segment(fmt, pos);
+		let size = match segment {
+			Segment::End => break,
+			Segment::Literal { start, end } => end - start,
+			Segment::Escaped { .. } => 1,
+			Segment::Error(_) => 0,
+			Segment::Slot { name, spec } => {
+				let index = if name.0 == name.1 {
+					slots += 1;
+					slots - 1
+				} else {
+					match find_name(fmt, name, names) {
+						Some(i) => positional + i,
+						None => panic!("format strings are measured first"),
+					}
+				};
+
+				out[len] = Piece::Arg {
+					index,
+					spec: Spec::parse(fmt, spec.0, spec.1),
+					last: true,
+				};
+				len += 1;
+				in_text = false;
+				pos = next;
+				continue;
+			}
+		};
+
+		if in_text {
+			if let Piece::Text { end, .. } = &mut out[len - 1] {
+				*end += size;
+			}
+		} else {
+			out[len] = Piece::Text {
+				start: text,
+				end: text + size,
+			};
+			len += 1;
+			in_text = true;
+		}
+
+		text += size;
+		pos = next;
+	}
+
+	// Every slot but the last one for an argument has to clone it.
+	let mut i = 0;
+	while i < N {
+		if let Piece::Arg { index, .. } = out[i] {
+			let mut j = i + 1;
+			while j < N {
+				if let Piece::Arg { index: other, .. } = out[j] {
+					if other == index {
+						if let Piece::Arg { last, .. } = &mut out[i] {
+							*last = false;
+						}
+					}
+				}
+				j += 1;
+			}
+		}
+		i += 1;
+	}
+
+	out
+}
+
+/// Converts the [unescaped text][text()] of a format string to a string.
+pub const fn as_str(text: &[u8]) -> &str {
+	match std::str::from_utf8(text) {
+		Ok(text) => text,
+		Err(_) => panic!("escapes only remove ASCII braces"),
+	}
+}
+
+/// Builds a component from the pieces of a format string and its arguments.
+pub fn build(text: &str, pieces: &[Piece], args: &mut [Option<Component>]) -> Component {
+	let mut components = pieces.iter().map(|piece| piece.build(text, args));
+
+	if pieces.len() == 1 {
+		components.next().expect("one piece is present")
+	} else {
+		Component::empty().with_extra(components)
+	}
+}
+
+/// Creates a [Component] from a format string, in the style of [format!]. Each slot in the format
+/// string is filled with an argument, and can apply style shortcuts to it.
+///
+/// [component!][crate::component!] is a shorter alias for this macro.
+///
+/// # Syntax
+/// * `{}` is filled with the next positional argument.
+/// * `{name}` is filled with the argument passed as `name = value`. Unlike [format!], variables
+///   are not captured implicitly.
+/// * `{name:style,...}` and `{:style,...}` also apply styles to the argument. Styles are named
+///   colors (`gold`, `dark_red`, ...), hex colors (`#FFAA00`), and the decorations `bold`,
+///   `italic`, `underlined`, `strikethrough` and `obfuscated`. Decorations can be turned off with
+///   a leading `!`, such as `!italic`.
+/// * `{{` and `}}` are literal braces.
+///
+/// Arguments can be anything that implements [`Into<Component>`][Into], or failing that,
+/// [Display]. The style from a slot is applied underneath an argument's own style, so a
+/// component argument's own color takes precedence over a color in the slot.
+///
+/// Invalid styles, unknown, unused or duplicate named arguments, positional arguments after named
+/// ones, and a mismatched number of positional arguments are all compile errors. The format string
+/// is parsed at compile time, so at runtime this only builds the resulting components.
+///
+/// # Examples
+/// ```
+/// use typewheel::{format_component, Component, TextColor};
+///
+/// let player = "Notch";
+/// let component = format_component!("{player:gold,bold} joined with {} friends", 3, player = player);
+///
+/// assert_eq!(
+///     component,
+///     Component::empty().with_extra([
+///         Component::text("Notch").with_color(TextColor::Gold).with_bold(true),
+///         Component::text(" joined with "),
+///         Component::text("3"),
+///         Component::text(" friends"),
+///     ])
+/// );
+/// ```
+///
+/// Mistakes in the format string are caught at compile time:
+/// ```compile_fail
+/// # use typewheel::format_component;
+/// let component = format_component!("{:blod}", "typo");
+/// ```
+///
+/// ```compile_fail
+/// # use typewheel::format_component;
+/// let component = format_component!("{name}", name = "a", name = "b");
+/// ```
+///
+/// ```compile_fail
+/// # use typewheel::format_component;
+/// let component = format_component!("{name} {}", name = "a", "b");
+/// ```
+#[macro_export]
+macro_rules! format_component {
+	($fmt: literal $(,)?) => {
+		$crate::format_component!(@parse $fmt [] [] [])
+	};
+
+	($fmt: literal, $($args: tt)+) => {
+		$crate::format_component!(@parse $fmt [] [] [] $($args)+)
+	};
+
+	// Named arguments. These have to be matched before positional ones, since `name = value` is
+	// also a valid expression.
+	(@parse $fmt: literal [$($pos: expr,)*] [$($name: ident = $value: expr,)*] [$($late: expr,)*]
+		$arg: ident = $arg_value: expr $(, $($rest: tt)*)?) => {
+		$crate::format_component!(
+			@parse $fmt [$($pos,)*] [$($name = $value,)* $arg = $arg_value,] [$($late,)*]
+			$($($rest)*)?
+		)
+	};
+
+	// Positional arguments that follow named ones are collected separately, so that they can be
+	// rejected with a proper error.
+	(@parse $fmt: literal [$($pos: expr,)*] [$($name: ident = $value: expr,)+] [$($late: expr,)*]
+		$arg: expr $(, $($rest: tt)*)?) => {
+		$crate::format_component!(
+			@parse $fmt [$($pos,)*] [$($name = $value,)*] [$($late,)* $arg,] $($($rest)*)?
+		)
+	};
+
+	(@parse $fmt: literal [$($pos: expr,)*] [] [] $arg: expr $(, $($rest: tt)*)?) => {
+		$crate::format_component!(@parse $fmt [$($pos,)* $arg,] [] [] $($($rest)*)?)
+	};
+
+	(@parse $fmt: literal [$($pos: expr,)*] [$($name: ident = $value: expr,)*] [$($late: expr,)*]) => {{
+		#[allow(unused_imports)]
+		use $crate::format::{ViaComponent as _, ViaDisplay as _};
+
+		const FMT: &str = $fmt;
+		const NAMES: &[&str] = &[$(stringify!($name)),*];
+		const POSITIONAL: usize = <[&str]>::len(&[$(stringify!($pos)),*]);
+		const SIZES: (usize, usize) = $crate::format::measure(
+			FMT,
+			NAMES,
+			POSITIONAL,
+			<[&str]>::len(&[$(stringify!($late)),*]),
+		);
+		const TEXT: &str = $crate::format::as_str(&$crate::format::text::<{ SIZES.1 }>(FMT));
+		const PIECES: [$crate::format::Piece; SIZES.0] =
+			$crate::format::pieces(FMT, NAMES, POSITIONAL);
+
+		$crate::format::build(
+			TEXT,
+			&PIECES,
+			&mut [
+				$(Some($crate::format::Arg($pos).__into_component()),)*
+				$(Some($crate::format::Arg($value).__into_component()),)*
+			],
+		)
+	}};
+}
+
+/// An alias for [format_component!][crate::format_component!]. See its documentation for the
+/// format string syntax.
+///
+/// # Examples
+/// ```
+/// use typewheel::{component, Component, TextColor};
+///
+/// let component = component!("Hello, {name:aqua}!", name = "world");
+/// assert_eq!(
+///     component,
+///     Component::empty().with_extra([
+///         Component::text("Hello, "),
+///         Component::text("world").with_color(TextColor::Aqua),
+///         Component::text("!"),
+///     ])
+/// );
+/// ```
+#[macro_export]
+macro_rules! component {
+	($($args: tt)*) => {
+		$crate::format_component!($($args)*)
+	};
+}
@@ -0,0 +1,172 @@
+//! A module for interning components. An [Interner] stores each distinct node once, and hands out
+//! [SharedComponent] handles to it that are cheap to clone, compare and hash.
+//!
+//! Components are interned bottom-up: each child, translation argument and hover text is interned
+//! before the node it belongs to, so identical subtrees are stored once, even when they are part of
+//! different trees. Interning is useful when the same messages are built or received over and
+//! over, such as formatted chat messages or item lore, and need to be cached or deduplicated.
+//!
+//! # Examples
+//! ```
+//! use typewheel::Component;
+//! use typewheel::intern::Interner;
+//! use typewheel::shared::SharedComponent;
+//!
+//! let mut interner = Interner::new();
+//! let a = interner.intern(Component::text("hello").with_bold(true));
+//! let b = interner.intern(Component::text("hello").with_bold(true));
+//!
+//! assert!(SharedComponent::ptr_eq(&a, &b));
+//! assert_eq!(interner.len(), 1);
+//! ```
+
+use crate::shared::SharedComponent;
+use crate::Component;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::slice;
+
+/// A store of distinct component nodes. See the [module docs][self] for more information.
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+	nodes: HashSet<Entry>,
+}
+
+impl Interner {
+	/// Creates a new, empty interner.
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Interns a component, returning a handle to the stored copy. Every node in the component is
+	/// interned, starting with the components nested in it, and nodes that are equal to an already
+	/// interned node reuse the existing copy.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::Component;
+	/// use typewheel::intern::Interner;
+	/// use typewheel::shared::SharedComponent;
+	///
+	/// let mut interner = Interner::new();
+	/// let a = interner.intern(Component::text("a").with_extra(["shared"]));
+	/// let b = interner.intern(Component::text("b").with_extra(["shared"]));
+	///
+	/// assert!(SharedComponent::ptr_eq(&a.extra()[0], &b.extra()[0]));
+	/// assert_eq!(interner.len(), 3);
+	/// ```
+	pub fn intern(&mut self, component: Component) -> SharedComponent {
+		let node = SharedComponent::try_build(component, &mut |nested| Some(self.intern(nested)))
+			.expect("interning a component always succeeds");
+
+		let entry = Entry(node);
+		if let Some(Entry(existing)) = self.nodes.get(&entry) {
+			return existing.clone();
+		}
+
+		self.nodes.insert(entry.clone());
+		entry.0
+	}
+
+	/// Gets a handle to an interned component equal to the provided one, if there is one.
+	pub fn get(&self, component: &Component) -> Option<SharedComponent> {
+		self.find(component.clone())
+	}
+
+	fn find(&self, component: Component) -> Option<SharedComponent> {
+		let node = SharedComponent::try_build(component, &mut |nested| self.find(nested))?;
+		self.nodes
+			.get(&Entry(node))
+			.map(|Entry(existing)| existing.clone())
+	}
+
+	/// Gets the number of distinct nodes in this interner.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.nodes.len()
+	}
+
+	/// Checks if this interner is empty.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.nodes.is_empty()
+	}
+
+	/// Removes every node that is no longer referenced outside of this interner, either by a handle
+	/// or as the child of another node that is still in use.
+	///
+	/// # Examples
+	/// ```
+	/// # use typewheel::{Component, intern::Interner};
+	/// let mut interner = Interner::new();
+	/// let kept = interner.intern(Component::text("a"));
+	/// interner.intern(Component::text("b").with_extra(["c"]));
+	///
+	/// interner.collect_unused();
+	/// assert_eq!(interner.len(), 1);
+	/// assert!(interner.get(&Component::text("a")).is_some());
+	/// ```
+	pub fn collect_unused(&mut self) {
+		// Removing a node releases its children, which may then be unused too.
+		loop {
+			let len = self.nodes.len();
+			self.nodes
+				.retain(|Entry(node)| SharedComponent::strong_count(node) > 1);
+
+			if self.nodes.len() == len {
+				break;
+			}
+		}
+	}
+
+	/// Removes every node from this interner. Existing handles stay valid.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.nodes.clear();
+	}
+}
+
+/// An interned node. Since the components nested in an interned node are always interned too,
+/// nodes are hashed and compared by their addresses rather than by walking their subtrees.
+#[derive(Clone, Debug)]
+struct Entry(SharedComponent);
+
+impl PartialEq for Entry {
+	fn eq(&self, other: &Self) -> bool {
+		let (a, b) = (&self.0, &other.0);
+
+		a.content() == b.content()
+			&& a.style() == b.style()
+			&& same_nodes(a.args(), b.args())
+			&& same_nodes(hover_text(a), hover_text(b))
+			&& same_nodes(a.extra(), b.extra())
+	}
+}
+
+impl Eq for Entry {}
+
+impl Hash for Entry {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.0.content().hash(state);
+		self.0.style().hash(state);
+
+		let nested = [self.0.args(), hover_text(&self.0), self.0.extra()];
+		for nodes in nested {
+			state.write_usize(nodes.len());
+			for node in nodes {
+				node.as_ptr().hash(state);
+			}
+		}
+	}
+}
+
+/// Gets the hover text of a node as a list of at most one node.
+fn hover_text(node: &SharedComponent) -> &[SharedComponent] {
+	node.hover_text().map_or(&[], slice::from_ref)
+}
+
+/// Checks if two lists hold the same nodes, by identity.
+fn same_nodes(a: &[SharedComponent], b: &[SharedComponent]) -> bool {
+	a.len() == b.len() && a.iter().zip(b).all(|(a, b)| SharedComponent::ptr_eq(a, b))
+}
@@ -1,20 +1,35 @@
-//! A module containing two iterator types: [FlatIterator] and [VisitingIterator]. These types
-//! provide support for iterating over components and their children (extra).
+//! A module containing three iterator types: [FlatIterator], [VisitingIterator] and
+//! [StyledIterator]. These types provide support for iterating over components and their children
+//! (extra).
 //!
-//! Component iterators can be accessed in 3 ways:
+//! Component iterators can be accessed in 4 ways:
 //! * [Component::iter()] – Provides an implementation of [`Iterator<Item = &Component>`][Iterator].
-//! * [Component::into_iter()] – Same as above but useful for use in `for` loops.
+//! * [`<&Component>::into_iter()`][FlatIterator] – Same as above but useful for use in `for` loops.
 //! * [Component::visit()] – Provides a [VisitingIterator] instance. See the struct docs for more
-//! information.
+//!   information.
+//! * [Component::iter_styled()] – Provides a [StyledIterator] instance, which resolves the
+//!   effective style of every node.
+//!
+//! Trees can also be edited while traversing them with [Component::iter_mut()] and
+//! [Component::visit_mut()], or consumed node by node with [Component::into_iter()] and
+//! [Component::into_visit()].
 
+mod owned;
+mod styled;
 mod tree;
 mod visit;
+mod walk;
 
 pub use self::{
+	owned::{IntoIter, IntoVisitingIterator, OwnedVisit},
+	styled::{StyledIterator, StyledNode},
 	tree::FlatIterator,
 	visit::{Visit, VisitingIterator},
+	walk::{IterMut, MutWalker, NodeMut, Rewrite},
 };
-use crate::Component;
+use crate::path::{ComponentPath, PathStep};
+use crate::{Component, Content, HoverEvent, Style};
+use std::collections::VecDeque;
 
 /// Determines the order over which a component iterator runs through child components.
 ///
@@ -32,7 +47,7 @@ use crate::Component;
 ///     println!("{}", child.shallow_text().unwrap_or(""));
 /// }
 /// ```
-#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum IterOrder {
 	/// Depth-first iteration order. When used in an iterator, the tree is traversed by visiting
@@ -92,6 +107,206 @@ pub enum IterOrder {
 	/// 7. g
 	/// 8. h
 	BreadthFirst,
+
+	/// Depth-first post-order. Like [depth-first ordering][IterOrder::DepthFirst], except that a
+	/// component is visited *after* all of its children instead of before them. This is useful for
+	/// computing values from the bottom of a tree up, since a node's children have always been
+	/// seen by the time the node itself is.
+	///
+	/// # Examples
+	/// Take the following component tree, where each letter denotes a component:
+	/// ```monospace
+	/// a
+	/// ╠═ b
+	/// ║  ╚═ c, d
+	/// ╚═ e
+	///    ╚═ f
+	///       ╚═ g, h
+	/// ```
+	///
+	/// The components in this graph will be traversed in this order:
+	/// 1. c
+	/// 2. d
+	/// 3. b
+	/// 4. g
+	/// 5. h
+	/// 6. f
+	/// 7. e
+	/// 8. a
+	PostOrder,
+
+	/// Reverse depth-first order. Components are visited in the exact opposite order of
+	/// [depth-first ordering][IterOrder::DepthFirst], which means that they are traversed from the
+	/// last one displayed on-screen to the first.
+	///
+	/// # Examples
+	/// Take the following component tree, where each letter denotes a component:
+	/// ```monospace
+	/// a
+	/// ╠═ b
+	/// ║  ╚═ c, d
+	/// ╚═ e
+	///    ╚═ f
+	///       ╚═ g, h
+	/// ```
+	///
+	/// The components in this graph will be traversed in this order:
+	/// 1. h
+	/// 2. g
+	/// 3. f
+	/// 4. e
+	/// 5. d
+	/// 6. c
+	/// 7. b
+	/// 8. a
+	ReverseDepthFirst,
+}
+
+impl IterOrder {
+	/// Queues up the children of a node that was just taken from the front of an iterator's queue.
+	/// Returns the node if it should be yielded right away, or [None] if it was queued to be
+	/// yielded after its children.
+	pub(crate) fn expand<E, N>(
+		self,
+		queue: &mut VecDeque<Pending<E, N>>,
+		node: N,
+		children: impl DoubleEndedIterator<Item = E>,
+	) -> Option<N> {
+		match self {
+			Self::BreadthFirst => {
+				queue.extend(children.map(Pending::Expand));
+				Some(node)
+			}
+
+			// Children are pushed to the front in reverse, so that they end up in the right order
+			// in the actual queue.
+			Self::DepthFirst => {
+				for child in children.rev() {
+					queue.push_front(Pending::Expand(child));
+				}
+
+				Some(node)
+			}
+			Self::PostOrder => {
+				queue.push_front(Pending::Emit(node));
+				for child in children.rev() {
+					queue.push_front(Pending::Expand(child));
+				}
+
+				None
+			}
+			Self::ReverseDepthFirst => {
+				queue.push_front(Pending::Emit(node));
+				for child in children {
+					queue.push_front(Pending::Expand(child));
+				}
+
+				None
+			}
+		}
+	}
+
+	/// Takes the next node to yield from the front of an iterator's queue, expanding nodes along
+	/// the way. This is shared by the iterators whose queued nodes are references, so that they
+	/// only differ in how they get the children of a node.
+	pub(crate) fn advance<E, I>(
+		self,
+		queue: &mut VecDeque<Pending<E, E>>,
+		mut children: impl FnMut(E) -> I,
+	) -> Option<E>
+	where
+		E: Copy,
+		I: DoubleEndedIterator<Item = E>,
+	{
+		loop {
+			let node = match queue.pop_front()? {
+				Pending::Expand(node) => node,
+				Pending::Emit(node) => return Some(node),
+			};
+
+			// Reserve room for every child and the node itself all at once.
+			let children = children(node);
+			queue.reserve(children.size_hint().0 + 1);
+
+			if let Some(node) = self.expand(queue, node, children) {
+				return Some(node);
+			}
+		}
+	}
+}
+
+/// An adapter that tags each item of a component iterator with the role of its node: the
+/// [step][PathStep] from the node's parent to it, or [None] for the root. Created by
+/// [FlatIterator::tagged()] and [VisitingIterator::tagged()].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Tagged<I> {
+	inner: I,
+}
+
+impl<I> Tagged<I> {
+	#[inline]
+	pub(crate) fn new(inner: I) -> Self {
+		Self { inner }
+	}
+}
+
+/// Gets the nodes nested directly in a node, along with the step to each one. Nodes are returned
+/// in the order that they are serialized: hover text first, then translation arguments, then
+/// children.
+pub(crate) fn nested(
+	node: &Component,
+	translate_args: bool,
+	hover_text: bool,
+) -> impl DoubleEndedIterator<Item = (PathStep, &Component)> {
+	let hover = match (hover_text, &node.style.hover_event) {
+		(true, Some(HoverEvent::ShowText(text))) => Some((PathStep::HoverText, &**text)),
+		(true, Some(HoverEvent::ShowEntity(entity))) => Some((PathStep::EntityName, &entity.name)),
+		_ => None,
+	};
+
+	let args = match (translate_args, &node.content) {
+		(true, Content::Translation { with, .. }) => with.as_slice(),
+		_ => &[],
+	};
+
+	hover
+		.into_iter()
+		.chain(
+			args.iter()
+				.enumerate()
+				.map(|(i, arg)| (PathStep::Arg(i), arg)),
+		)
+		.chain(
+			node.extra
+				.iter()
+				.enumerate()
+				.map(|(i, child)| (PathStep::Extra(i), child)),
+		)
+}
+
+/// Counts the nodes in a tree, including nested nodes that are [included][nested()]. Nodes are
+/// counted with an explicit stack, so that deep trees can't overflow the call stack.
+pub(crate) fn count(node: &Component, translate_args: bool, hover_text: bool) -> usize {
+	let mut stack = vec![node];
+	let mut count = 0;
+
+	while let Some(node) = stack.pop() {
+		count += 1;
+		stack.extend(nested(node, translate_args, hover_text).map(|(_, child)| child));
+	}
+
+	count
+}
+
+/// An entry in the queue of a variable-order iterator.
+#[derive(Clone, Debug)]
+pub(crate) enum Pending<E, N> {
+	/// A node whose children have not been queued yet.
+	Expand(E),
+
+	/// A node whose children have already been queued, and that only needs to be yielded.
+	Emit(N),
 }
 
 impl Component {
@@ -111,7 +326,7 @@ impl Component {
 	/// }
 	/// ```
 	#[inline(always)]
-	pub fn iter(&self) -> FlatIterator {
+	pub fn iter(&self) -> FlatIterator<'_> {
 		FlatIterator::new(self)
 	}
 
@@ -122,7 +337,72 @@ impl Component {
 	///
 	/// This method returns an [Iterator] of [Visit] items.
 	#[inline(always)]
-	pub fn visit(&self) -> VisitingIterator {
+	pub fn visit(&self) -> VisitingIterator<'_> {
 		VisitingIterator::new(self)
 	}
+
+	/// Consumes this component and creates an iterator that emits [OwnedVisit] operations. See the
+	/// [IntoVisitingIterator] docs for more information.
+	#[inline(always)]
+	pub fn into_visit(self) -> IntoVisitingIterator {
+		IntoVisitingIterator::new(self)
+	}
+
+	/// Creates an iterator of mutable references to the content and style of every node.
+	/// Components are traversed in the provided order. See the [IterMut] docs for more
+	/// information.
+	#[inline(always)]
+	pub fn iter_mut(&mut self) -> IterMut<'_> {
+		IterMut::new(self)
+	}
+
+	/// Creates a walker that visits every node mutably, and can remove, replace, or splice nodes
+	/// during traversal. See the [MutWalker] docs for more information.
+	#[inline(always)]
+	pub fn visit_mut(&mut self) -> MutWalker<'_> {
+		MutWalker::new(self)
+	}
+
+	/// Creates a depth-first iterator that yields every node along with its effective style and
+	/// depth. See the [StyledIterator] docs for more information.
+	#[inline(always)]
+	pub fn iter_styled(&self) -> StyledIterator<'_> {
+		StyledIterator::new(self)
+	}
+
+	/// Gets the effective style of the node at a path. Translation arguments inherit the style of
+	/// their translation, while hover text starts out unstyled. Returns [None] if the path does not
+	/// point to a node.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::{Component, Style, TextColor};
+	/// use typewheel::path::ComponentPath;
+	///
+	/// let component = Component::text("a")
+	///     .with_color(TextColor::Red)
+	///     .with_extra([Component::text("b").with_extra([Component::text("c").with_bold(true)])]);
+	///
+	/// let path = ComponentPath::root().child(0).child(0);
+	/// let style = component.effective_style_at(&path).unwrap();
+	/// assert_eq!(style.color, Some(TextColor::Red));
+	/// assert_eq!(style.bold, Some(true));
+	///
+	/// assert_eq!(component.effective_style_at(&ComponentPath::root().child(1)), None);
+	/// ```
+	pub fn effective_style_at(&self, path: &ComponentPath) -> Option<Style> {
+		let mut node = self;
+		let mut style = self.style.clone();
+
+		for &step in path.steps() {
+			node = step.get(node)?;
+			if matches!(step, PathStep::HoverText | PathStep::EntityName) {
+				style = Style::default();
+			}
+
+			style.merge(&node.style);
+		}
+
+		Some(style)
+	}
 }
@@ -0,0 +1,177 @@
+use super::{IterOrder, Pending};
+use crate::Component;
+use std::collections::VecDeque;
+use std::iter::FusedIterator;
+
+/// A variable-order iterator that consumes a [Component] tree and yields its nodes by value. This
+/// is the owning equivalent of a [FlatIterator][super::FlatIterator], and is created by
+/// [Component::into_iter()].
+///
+/// Each yielded node is detached from its children, which are yielded separately. Content and
+/// styles are moved out of the tree rather than cloned, so no strings are copied.
+///
+/// # Examples
+/// ```
+/// use typewheel::Component;
+///
+/// let component = Component::text("a").with_extra([Component::text("b").with_extra(["c"])]);
+/// let nodes: Vec<Component> = component.into_iter().collect();
+///
+/// assert_eq!(nodes, [Component::text("a"), Component::text("b"), Component::text("c")]);
+/// ```
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntoIter {
+	queue: VecDeque<Pending<Component, Component>>,
+	order: IterOrder,
+}
+
+impl IntoIter {
+	/// Sets the [iteration order][IterOrder] for this iterator.
+	pub fn with_order(mut self, order: IterOrder) -> Self {
+		self.order = order;
+		self
+	}
+}
+
+impl Iterator for IntoIter {
+	type Item = Component;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			let mut node = match self.queue.pop_front()? {
+				Pending::Expand(node) => node,
+				Pending::Emit(node) => return Some(node),
+			};
+
+			let extra = std::mem::take(&mut node.extra);
+			self.queue.reserve(extra.len() + 1);
+
+			if let Some(node) = self.order.expand(&mut self.queue, node, extra.into_iter()) {
+				return Some(node);
+			}
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let remaining = self
+			.queue
+			.iter()
+			.map(|pending| match pending {
+				Pending::Expand(node) => node.tree_size(),
+				Pending::Emit(_) => 1,
+			})
+			.sum();
+
+		(remaining, Some(remaining))
+	}
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+impl FusedIterator for IntoIter {}
+
+impl IntoIterator for Component {
+	type Item = Component;
+	type IntoIter = IntoIter;
+
+	/// Consumes this component and creates an iterator over its nodes. See the [IntoIter] docs for
+	/// more information.
+	fn into_iter(self) -> Self::IntoIter {
+		IntoIter {
+			queue: VecDeque::from([Pending::Expand(self)]),
+			order: IterOrder::default(),
+		}
+	}
+}
+
+/// An iterator that consumes a [Component] tree and emits [OwnedVisit] operations. This is the
+/// owning equivalent of a [VisitingIterator][super::VisitingIterator], and is created by
+/// [Component::into_visit()].
+///
+/// Like an [IntoIter], each node is yielded by value and detached from its children. This makes it
+/// possible to rebuild a tree in another format, such as a list of runs or a different tree type,
+/// without copying any of its strings.
+///
+/// # Examples
+/// ```
+/// use typewheel::Component;
+/// use typewheel::iter::OwnedVisit;
+///
+/// let component = Component::text("a").with_extra(["b"]);
+/// let ops: Vec<_> = component.into_visit().collect();
+///
+/// assert_eq!(
+///     ops,
+///     [
+///         OwnedVisit::Push(Component::text("a")),
+///         OwnedVisit::Push(Component::text("b")),
+///         OwnedVisit::Pop,
+///         OwnedVisit::Pop,
+///     ]
+/// );
+/// ```
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntoVisitingIterator {
+	/// Pending operations, with the next one at the end. Subtrees are expanded when they are
+	/// reached.
+	stack: Vec<Option<Component>>,
+}
+
+impl IntoVisitingIterator {
+	#[inline]
+	pub(super) fn new(root: Component) -> Self {
+		Self {
+			stack: vec![Some(root)],
+		}
+	}
+}
+
+impl Iterator for IntoVisitingIterator {
+	type Item = OwnedVisit;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let Some(mut node) = self.stack.pop()? else {
+			return Some(OwnedVisit::Pop);
+		};
+
+		let extra = std::mem::take(&mut node.extra);
+
+		self.stack.reserve(extra.len() + 1);
+		self.stack.push(None);
+		self.stack.extend(extra.into_iter().rev().map(Some));
+
+		Some(OwnedVisit::Push(node))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		// Every node is pushed and popped once.
+		let remaining = self
+			.stack
+			.iter()
+			.map(|op| op.as_ref().map_or(1, |node| 2 * node.tree_size()))
+			.sum();
+
+		(remaining, Some(remaining))
+	}
+}
+
+impl ExactSizeIterator for IntoVisitingIterator {}
+
+impl FusedIterator for IntoVisitingIterator {}
+
+/// Represents an operation in an [IntoVisitingIterator]. This is the owning equivalent of a
+/// [Visit][super::Visit].
+// Boxing the node would cost an allocation per node, which is what this iterator is meant to avoid.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OwnedVisit {
+	/// Indicates that a node has been entered. The node is detached from its children, which are
+	/// pushed next.
+	Push(Component),
+
+	/// Indicates that the most recently pushed node that has not been popped yet, and all of its
+	/// children, have been consumed.
+	Pop,
+}
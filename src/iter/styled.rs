@@ -0,0 +1,108 @@
+use crate::{Component, Style};
+use std::iter::FusedIterator;
+use std::slice;
+
+/// A depth-first iterator over [Component] trees that resolves the effective style of every node.
+///
+/// A node's effective style is the result of merging the styles of all of its parents with its
+/// own, which is the style that it is actually displayed with. Styles are computed incrementally
+/// from the effective style of each node's parent, so the cost of iterating does not grow with the
+/// depth of the tree.
+///
+/// # Examples
+/// ```
+/// use typewheel::{Component, TextColor};
+///
+/// let component = Component::text("a")
+///     .with_color(TextColor::Red)
+///     .with_extra([Component::text("b").with_bold(true)]);
+///
+/// let nodes: Vec<_> = component.iter_styled().collect();
+/// assert_eq!(nodes[1].node, &Component::text("b").with_bold(true));
+/// assert_eq!(nodes[1].style.color, Some(TextColor::Red));
+/// assert_eq!(nodes[1].style.bold, Some(true));
+/// assert_eq!(nodes[1].depth, 1);
+/// ```
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct StyledIterator<'a> {
+	root: Option<&'a Component>,
+
+	/// The remaining children and effective style of every node that is currently being visited.
+	/// Nodes without children are never pushed.
+	stack: Vec<(slice::Iter<'a, Component>, Style)>,
+}
+
+impl<'a> StyledIterator<'a> {
+	#[inline]
+	pub(super) fn new(root: &'a Component) -> Self {
+		Self {
+			root: Some(root),
+			stack: Vec::new(),
+		}
+	}
+
+	fn enter(&mut self, node: &'a Component, style: Style, depth: usize) -> StyledNode<'a> {
+		if !node.extra.is_empty() {
+			self.stack.push((node.extra.iter(), style.clone()));
+		}
+
+		StyledNode { node, style, depth }
+	}
+}
+
+impl<'a> Iterator for StyledIterator<'a> {
+	type Item = StyledNode<'a>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if let Some(root) = self.root.take() {
+			return Some(self.enter(root, root.style.clone(), 0));
+		}
+
+		loop {
+			let depth = self.stack.len();
+			let (children, parent) = self.stack.last_mut()?;
+
+			match children.next() {
+				Some(child) => {
+					let mut style = parent.clone();
+					style.merge(&child.style);
+					return Some(self.enter(child, style, depth));
+				}
+				None => {
+					self.stack.pop();
+				}
+			}
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let root = self.root.map_or(0, Component::tree_size);
+		let rest = self
+			.stack
+			.iter()
+			.flat_map(|(children, _)| children.as_slice())
+			.map(Component::tree_size);
+
+		let remaining = root + rest.sum::<usize>();
+		(remaining, Some(remaining))
+	}
+}
+
+impl ExactSizeIterator for StyledIterator<'_> {}
+
+impl FusedIterator for StyledIterator<'_> {}
+
+/// A node yielded by a [StyledIterator].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct StyledNode<'a> {
+	/// The node itself.
+	pub node: &'a Component,
+
+	/// The effective style of the node.
+	pub style: Style,
+
+	/// The depth of the node in the tree. The root node has a depth of 0.
+	pub depth: usize,
+}
@@ -1,9 +1,12 @@
-use super::IterOrder;
-use crate::{Component, Content};
-use std::cmp::max;
+use super::{count, nested, IterOrder, Pending, Tagged};
+use crate::path::PathStep;
+use crate::Component;
 use std::collections::VecDeque;
 use std::iter::FusedIterator;
 
+/// A queued node, along with the step from its parent to it.
+type Entry<'a> = (Option<PathStep>, &'a Component);
+
 /// A variable-order iterator over [Component] trees. This iterator does *not* preserve information
 /// about the "shape" of the component tree. For a more structured iterator, use a [visiting
 /// iterator][visit].
@@ -12,23 +15,20 @@ use std::iter::FusedIterator;
 #[derive(Clone, Debug)]
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 pub struct FlatIterator<'a> {
-	queue: VecDeque<&'a Component>,
+	queue: VecDeque<Pending<Entry<'a>, Entry<'a>>>,
 	order: IterOrder,
 	include_translate_args: bool,
-
-	/// A lower bound for the iterator size hint. This is a conservative estimate and does not
-	/// represent the true size of the iterator. Default is extra.len().
-	size_hint: usize,
+	include_hover_text: bool,
 }
 
 impl<'a> FlatIterator<'a> {
 	#[inline]
 	pub(super) fn new(root: &'a Component) -> Self {
 		Self {
-			queue: VecDeque::from([root]),
+			queue: VecDeque::from([Pending::Expand((None, root))]),
 			order: IterOrder::default(),
 			include_translate_args: false,
-			size_hint: root.extra.len(),
+			include_hover_text: false,
 		}
 	}
 }
@@ -56,72 +56,114 @@ impl FlatIterator<'_> {
 		self
 	}
 
+	/// Sets a flag to include the text of [ShowText][crate::HoverEvent::ShowText] hover events
+	/// and the names of [ShowEntity][crate::HoverEvent::ShowEntity] hover events in the output of
+	/// this iterator. Hover text comes before a node's translation arguments and children, since
+	/// that is the order they are serialized in.
+	pub fn with_hover_text(mut self) -> Self {
+		self.include_hover_text = true;
+		self
+	}
+
 	/// Sets the [iteration order][IterOrder] for this iterator. See the [IterOrder] docs for more
 	/// information.
 	pub fn with_order(mut self, order: IterOrder) -> Self {
 		self.order = order;
 		self
 	}
+
+	/// Tags each node yielded by this iterator with its role: the [step][PathStep] from its parent
+	/// to it, or [None] for the root. This tells apart children, translation arguments and hover
+	/// text when they are included.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::{Component, HoverEvent};
+	/// use typewheel::path::PathStep;
+	///
+	/// let component = Component::translate("a", ["b"])
+	///     .with_hover_event(HoverEvent::show_text("c"))
+	///     .with_extra(["d"]);
+	///
+	/// let roles: Vec<_> = component
+	///     .iter()
+	///     .with_translate_args()
+	///     .with_hover_text()
+	///     .tagged()
+	///     .map(|(role, _)| role)
+	///     .collect();
+	///
+	/// assert_eq!(
+	///     roles,
+	///     [
+	///         None,
+	///         Some(PathStep::HoverText),
+	///         Some(PathStep::Arg(0)),
+	///         Some(PathStep::Extra(0)),
+	///     ]
+	/// );
+	/// ```
+	pub fn tagged(self) -> Tagged<Self> {
+		Tagged::new(self)
+	}
+}
+
+impl<'a> FlatIterator<'a> {
+	/// Advances the iterator, returning the next node along with its role.
+	fn next_tagged(&mut self) -> Option<Entry<'a>> {
+		let (args, hover) = (self.include_translate_args, self.include_hover_text);
+		self.order.advance(&mut self.queue, |(_, node)| {
+			nested(node, args, hover).map(|(step, child)| (Some(step), child))
+		})
+	}
 }
 
 impl<'a> Iterator for FlatIterator<'a> {
 	type Item = &'a Component;
 
+	#[inline]
 	fn next(&mut self) -> Option<Self::Item> {
-		let next = self.queue.pop_front();
-		if let Some(item) = next {
-			let extra = &item.extra;
-
-			match self.order {
-				IterOrder::BreadthFirst => {
-					if let (true, Content::Translation { with: args, .. }) =
-						(self.include_translate_args, &item.content)
-					{
-						// Reserve room for args and extra all at once.
-						self.queue.reserve(args.len() + extra.len());
-						self.queue.extend(args);
-					}
-
-					self.queue.extend(extra);
-				}
-
-				IterOrder::DepthFirst => {
-					if let (true, Content::Translation { with: args, .. }) =
-						(self.include_translate_args, &item.content)
-					{
-						self.queue.reserve(args.len() + extra.len());
-
-						for arg in args.iter().rev() {
-							self.queue.push_front(arg);
-						}
-					} else {
-						self.queue.reserve(extra.len());
-					}
-
-					// Reversed because we're pushing elements to the front. They end up in the
-					// right order in the actual queue.
-					for child in extra.iter().rev() {
-						self.queue.push_front(child);
-					}
-				}
-			}
-		}
-
-		next
+		self.next_tagged().map(|(_, node)| node)
 	}
 
 	fn size_hint(&self) -> (usize, Option<usize>) {
-		// It /is/ possible to compute the full tree size of a component, but because components are
-		// deserialized much more often than they are iterated, so the cost of traveling the tree
-		// again after serialization doesn't make sense.
-
-		// Consider using a custom JSON deserializer to compute the proper size at parse time
-		(max(self.size_hint, self.queue.len()), None)
+		// The count is taken on demand, so that creating an iterator does not walk the tree.
+		let (args, hover) = (self.include_translate_args, self.include_hover_text);
+		let remaining = self
+			.queue
+			.iter()
+			.map(|pending| match pending {
+				Pending::Expand((_, node)) => count(node, args, hover),
+				Pending::Emit(_) => 1,
+			})
+			.sum();
+
+		(remaining, Some(remaining))
 	}
 }
 
+impl ExactSizeIterator for FlatIterator<'_> {}
+
 impl FusedIterator for FlatIterator<'_> {}
 
+impl<'a> Iterator for Tagged<FlatIterator<'a>> {
+	type Item = (Option<PathStep>, &'a Component);
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.inner.next_tagged()
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.inner.size_hint()
+	}
+}
+
+impl ExactSizeIterator for Tagged<FlatIterator<'_>> {}
+
+impl FusedIterator for Tagged<FlatIterator<'_>> {}
+
 impl<'a> IntoIterator for &'a Component {
 	type Item = &'a Component;
 	type IntoIter = FlatIterator<'a>;
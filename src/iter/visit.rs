@@ -1,5 +1,6 @@
+use super::{count, nested, Tagged};
+use crate::path::PathStep;
 use crate::Component;
-use std::cmp::max;
 use std::collections::VecDeque;
 use std::iter::FusedIterator;
 
@@ -28,56 +29,194 @@ use std::iter::FusedIterator;
 ///     assert!(depth <= 3);
 /// }
 /// ```
+///
+/// Visiting iterators are double-ended, so the same operations can also be consumed in reverse
+/// with [Iterator::rev()]. The front and back of the iterator never overlap.
 #[derive(Clone, Debug)]
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 pub struct VisitingIterator<'a> {
-	queue: VecDeque<Visit<'a>>,
-	size_hint: usize,
+	queue: VecDeque<Op<'a>>,
+	include_translate_args: bool,
+	include_hover_text: bool,
+}
+
+/// An entry in the queue of a [VisitingIterator]. Each entry holds the step from its node's parent
+/// to the node, or [None] for the root.
+#[derive(Clone, Copy, Debug)]
+enum Op<'a> {
+	/// A whole subtree that has not been entered from either end yet. It stands for a push, the
+	/// operations of every nested node, and a pop.
+	Tree(Option<PathStep>, &'a Component),
+
+	/// A single operation that is left over after a subtree was entered from one end.
+	Single(Option<PathStep>, Visit<'a>),
 }
 
 impl<'a> VisitingIterator<'a> {
 	#[inline]
 	pub(super) fn new(root: &'a Component) -> Self {
 		Self {
-			queue: VecDeque::from([Visit::Push(root)]),
-			size_hint: root.extra.len(),
+			queue: VecDeque::from([Op::Tree(None, root)]),
+			include_translate_args: false,
+			include_hover_text: false,
 		}
 	}
+
+	/// Sets a flag to also visit the [arguments][crate::Content::Translation] of translation
+	/// components. Arguments are visited after their translation is pushed, and before its
+	/// children.
+	pub fn with_translate_args(mut self) -> Self {
+		self.include_translate_args = true;
+		self
+	}
+
+	/// Sets a flag to also visit the text of [ShowText][crate::HoverEvent::ShowText] hover events
+	/// and the names of [ShowEntity][crate::HoverEvent::ShowEntity] hover events. Hover text is
+	/// visited right after its node is pushed.
+	pub fn with_hover_text(mut self) -> Self {
+		self.include_hover_text = true;
+		self
+	}
+
+	/// Tags each operation yielded by this iterator with the role of its node. See [Tagged] for
+	/// more information.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::Component;
+	/// use typewheel::iter::Visit;
+	/// use typewheel::path::PathStep;
+	///
+	/// let component = Component::translate("a", ["b"]);
+	/// let mut iter = component.visit().with_translate_args().tagged();
+	///
+	/// assert_eq!(iter.next(), Some((None, Visit::Push(&component))));
+	/// assert_eq!(
+	///     iter.next(),
+	///     Some((Some(PathStep::Arg(0)), Visit::Push(&Component::text("b"))))
+	/// );
+	/// ```
+	pub fn tagged(self) -> Tagged<Self> {
+		Tagged::new(self)
+	}
+
+	/// Advances the iterator from the front, returning the next operation along with its role.
+	fn next_tagged(&mut self) -> Option<(Option<PathStep>, Visit<'a>)> {
+		let (role, item) = match self.queue.pop_front()? {
+			Op::Tree(role, item) => (role, item),
+			Op::Single(role, op) => return Some((role, op)),
+		};
+
+		let children = nested(item, self.include_translate_args, self.include_hover_text);
+
+		self.queue.reserve(children.size_hint().0 + 1);
+		self.queue.push_front(Op::Single(role, Visit::Pop(item)));
+
+		for (step, child) in children.rev() {
+			self.queue.push_front(Op::Tree(Some(step), child));
+		}
+
+		Some((role, Visit::Push(item)))
+	}
+
+	/// Advances the iterator from the back, returning the next operation along with its role.
+	fn next_back_tagged(&mut self) -> Option<(Option<PathStep>, Visit<'a>)> {
+		let (role, item) = match self.queue.pop_back()? {
+			Op::Tree(role, item) => (role, item),
+			Op::Single(role, op) => return Some((role, op)),
+		};
+
+		let children = nested(item, self.include_translate_args, self.include_hover_text);
+
+		self.queue.reserve(children.size_hint().0 + 1);
+		self.queue.push_back(Op::Single(role, Visit::Push(item)));
+		self.queue
+			.extend(children.map(|(step, child)| Op::Tree(Some(step), child)));
+
+		Some((role, Visit::Pop(item)))
+	}
 }
 
 impl<'a> Iterator for VisitingIterator<'a> {
 	type Item = Visit<'a>;
 
+	#[inline]
 	fn next(&mut self) -> Option<Self::Item> {
-		let op = self.queue.pop_front();
-		if let Some(Visit::Push(item)) = op {
-			let extra = &item.extra;
+		self.next_tagged().map(|(_, op)| op)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		// Every node is pushed and popped once.
+		let (args, hover) = (self.include_translate_args, self.include_hover_text);
+		let remaining = self
+			.queue
+			.iter()
+			.map(|op| match op {
+				Op::Tree(_, node) => 2 * count(node, args, hover),
+				Op::Single(..) => 1,
+			})
+			.sum();
 
-			self.queue.reserve(extra.len() + 1);
-			self.queue.push_front(Visit::Pop(item));
+		(remaining, Some(remaining))
+	}
+}
 
-			for child in extra.iter().rev() {
-				self.queue.push_front(Visit::Push(child));
-			}
-		}
+impl ExactSizeIterator for VisitingIterator<'_> {}
 
-		op
+impl DoubleEndedIterator for VisitingIterator<'_> {
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.next_back_tagged().map(|(_, op)| op)
+	}
+}
+
+impl FusedIterator for VisitingIterator<'_> {}
+
+impl<'a> Iterator for Tagged<VisitingIterator<'a>> {
+	type Item = (Option<PathStep>, Visit<'a>);
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.inner.next_tagged()
 	}
 
+	#[inline]
 	fn size_hint(&self) -> (usize, Option<usize>) {
-		(max(self.queue.len(), self.size_hint), None)
+		self.inner.size_hint()
 	}
 }
 
-impl FusedIterator for VisitingIterator<'_> {}
+impl DoubleEndedIterator for Tagged<VisitingIterator<'_>> {
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.inner.next_back_tagged()
+	}
+}
+
+impl ExactSizeIterator for Tagged<VisitingIterator<'_>> {}
+
+impl FusedIterator for Tagged<VisitingIterator<'_>> {}
 
 /// Represents an operation in a [VisitingIterator]. See the iterator docs for more information.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum Visit<'a> {
+///
+/// The node type defaults to [Component], and is only different when visiting other tree types such
+/// as [shared components][crate::shared::SharedComponent].
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub enum Visit<'a, T = Component> {
 	/// Indicates that a component has entered the context of a [VisitingIterator]. After all of its
 	/// children have been consumed, a [Self::Pop] value is emitted.
-	Push(&'a Component),
+	Push(&'a T),
 
 	/// Indicates that a component and all of its children have been consumed by a [VisitingIterator].
-	Pop(&'a Component),
+	Pop(&'a T),
+}
+
+// Implemented manually so that T is not required to be Clone or Copy.
+impl<T> Clone for Visit<'_, T> {
+	#[inline(always)]
+	fn clone(&self) -> Self {
+		*self
+	}
 }
+
+impl<T> Copy for Visit<'_, T> {}
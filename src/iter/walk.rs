@@ -0,0 +1,259 @@
+use super::{IterOrder, Pending};
+use crate::{Component, Content, HoverEvent, Style};
+use std::collections::VecDeque;
+use std::iter::FusedIterator;
+
+/// A variable-order iterator over mutable references to the content and style of every node in a
+/// [Component] tree. This is the mutable equivalent of a [FlatIterator][super::FlatIterator].
+///
+/// Children can't be changed while iterating, since they are still waiting to be visited. To
+/// restructure a tree, use a [MutWalker] instead.
+///
+/// # Examples
+/// ```
+/// use typewheel::{Component, TextColor};
+///
+/// let mut component = Component::text("a").with_extra(["b", "c"]);
+/// for node in component.iter_mut() {
+///     node.style.color = Some(TextColor::Red);
+/// }
+///
+/// assert!(component.iter().all(|node| node.style.color == Some(TextColor::Red)));
+/// ```
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IterMut<'a> {
+	queue: VecDeque<Pending<&'a mut Component, NodeMut<'a>>>,
+	order: IterOrder,
+}
+
+impl<'a> IterMut<'a> {
+	#[inline]
+	pub(super) fn new(root: &'a mut Component) -> Self {
+		Self {
+			queue: VecDeque::from([Pending::Expand(root)]),
+			order: IterOrder::default(),
+		}
+	}
+
+	/// Sets the [iteration order][IterOrder] for this iterator.
+	pub fn with_order(mut self, order: IterOrder) -> Self {
+		self.order = order;
+		self
+	}
+}
+
+impl<'a> Iterator for IterMut<'a> {
+	type Item = NodeMut<'a>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			let Component {
+				content,
+				style,
+				extra,
+			} = match self.queue.pop_front()? {
+				Pending::Expand(node) => node,
+				Pending::Emit(node) => return Some(node),
+			};
+
+			let node = NodeMut { content, style };
+			if let Some(node) = self.order.expand(&mut self.queue, node, extra.iter_mut()) {
+				return Some(node);
+			}
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let remaining = self
+			.queue
+			.iter()
+			.map(|pending| match pending {
+				Pending::Expand(node) => node.tree_size(),
+				Pending::Emit(_) => 1,
+			})
+			.sum();
+
+		(remaining, Some(remaining))
+	}
+}
+
+impl ExactSizeIterator for IterMut<'_> {}
+
+impl FusedIterator for IterMut<'_> {}
+
+/// The mutable parts of a node yielded by an [IterMut].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct NodeMut<'a> {
+	/// The node's content.
+	pub content: &'a mut Content,
+
+	/// The node's style.
+	pub style: &'a mut Style,
+}
+
+/// An action returned from a [MutWalker] callback, which determines what happens to the node that
+/// was just visited.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Rewrite {
+	/// Keeps the node, including any changes made to it, and visits its children next. This is the
+	/// default.
+	#[default]
+	Continue,
+
+	/// Keeps the node, but does not visit its children.
+	Skip,
+
+	/// Removes the node from its parent.
+	Remove,
+
+	/// Replaces the node with another one. The replacement is not visited.
+	Replace(Component),
+
+	/// Replaces the node with any number of siblings. The new nodes are not visited.
+	Splice(Vec<Component>),
+}
+
+/// A walker that visits every node in a [Component] tree mutably, and can rewrite the tree as it
+/// goes. Created by [Component::visit_mut()].
+///
+/// Nodes are visited depth-first, in the order that they are serialized: a node is visited first,
+/// then its hover text and translation arguments (if enabled), and then its children. Each node is
+/// passed to a callback, which can edit it freely and returns a [Rewrite] that decides what
+/// happens to it next. Since a node is visited before its children, changes to its children are
+/// reflected in the rest of the walk.
+///
+/// When the root node is removed, it is replaced with an [empty][Component::empty()] component.
+/// The same goes for hover text and entity names, since they can only hold a single node. Splicing
+/// in their place wraps the new nodes in an empty component.
+///
+/// # Examples
+/// ```
+/// use typewheel::{Component, Content};
+/// use typewheel::iter::Rewrite;
+///
+/// let mut component = Component::text("keep").with_extra([
+///     Component::text("drop"),
+///     Component::keybind("key.jump"),
+///     Component::text("keep"),
+/// ]);
+///
+/// component.visit_mut().apply(|node| match &node.content {
+///     Content::Text(text) if text == "drop" => Rewrite::Remove,
+///     Content::Keybind(_) => Rewrite::Splice(vec!["[".into(), "jump".into(), "]".into()]),
+///     _ => Rewrite::Continue,
+/// });
+///
+/// assert_eq!(
+///     component,
+///     Component::text("keep").with_extra(["[", "jump", "]", "keep"])
+/// );
+/// ```
+#[derive(Debug)]
+#[must_use = "walkers do nothing until applied"]
+pub struct MutWalker<'a> {
+	root: &'a mut Component,
+	include_translate_args: bool,
+	include_hover_text: bool,
+}
+
+impl<'a> MutWalker<'a> {
+	#[inline]
+	pub(super) fn new(root: &'a mut Component) -> Self {
+		Self {
+			root,
+			include_translate_args: false,
+			include_hover_text: false,
+		}
+	}
+
+	/// Sets a flag to also visit the [arguments][Content::Translation] of translation components.
+	pub fn with_translate_args(mut self) -> Self {
+		self.include_translate_args = true;
+		self
+	}
+
+	/// Sets a flag to also visit the text of [ShowText][HoverEvent::ShowText] hover events and the
+	/// names of [ShowEntity][HoverEvent::ShowEntity] hover events.
+	pub fn with_hover_text(mut self) -> Self {
+		self.include_hover_text = true;
+		self
+	}
+
+	/// Walks the tree, calling `f` on every node.
+	pub fn apply(self, mut f: impl FnMut(&mut Component) -> Rewrite) {
+		let options = (self.include_translate_args, self.include_hover_text);
+		walk_single(self.root, options, &mut f);
+	}
+}
+
+type Options = (bool, bool);
+
+/// Walks a slot that can only hold a single node.
+fn walk_single(
+	node: &mut Component,
+	options: Options,
+	f: &mut impl FnMut(&mut Component) -> Rewrite,
+) {
+	match f(node) {
+		Rewrite::Continue => descend(node, options, f),
+		Rewrite::Skip => {}
+		Rewrite::Remove => *node = Component::empty(),
+		Rewrite::Replace(replacement) => *node = replacement,
+		Rewrite::Splice(nodes) => *node = Component::empty().with_extra(nodes),
+	}
+}
+
+/// Walks a list of sibling nodes.
+fn walk_list(
+	nodes: &mut Vec<Component>,
+	options: Options,
+	f: &mut impl FnMut(&mut Component) -> Rewrite,
+) {
+	let mut i = 0;
+
+	while i < nodes.len() {
+		match f(&mut nodes[i]) {
+			Rewrite::Continue => {
+				descend(&mut nodes[i], options, f);
+				i += 1;
+			}
+			Rewrite::Skip => i += 1,
+			Rewrite::Remove => {
+				nodes.remove(i);
+			}
+			Rewrite::Replace(replacement) => {
+				nodes[i] = replacement;
+				i += 1;
+			}
+			Rewrite::Splice(spliced) => {
+				let len = spliced.len();
+				nodes.splice(i..=i, spliced);
+				i += len;
+			}
+		}
+	}
+}
+
+/// Walks everything nested in a node that has already been visited.
+fn descend(node: &mut Component, options: Options, f: &mut impl FnMut(&mut Component) -> Rewrite) {
+	let (translate_args, hover_text) = options;
+
+	if hover_text {
+		match &mut node.style.hover_event {
+			Some(HoverEvent::ShowText(text)) => walk_single(text, options, f),
+			Some(HoverEvent::ShowEntity(entity)) => walk_single(&mut entity.name, options, f),
+			_ => {}
+		}
+	}
+
+	if translate_args {
+		if let Content::Translation { with, .. } = &mut node.content {
+			walk_list(with, options, f);
+		}
+	}
+
+	walk_list(&mut node.extra, options, f);
+}
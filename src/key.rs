@@ -23,7 +23,7 @@ use std::fmt::{Debug, Display, Formatter};
 /// # Serial Representation
 /// This type implements [serde]'s [Serialize] and [Deserialize], where it is encoded as a string.
 /// The serial representation is equivalent to the output of [ToString::to_string].
-#[derive(Deserialize, Clone, PartialEq, Eq)]
+#[derive(Deserialize, Clone, PartialEq, Eq, Hash)]
 #[serde(from = "String")]
 pub struct Key(Option<String>, String);
 
@@ -24,14 +24,34 @@
 //! assert!(!component.extra.is_empty());
 //! ```
 //!
+//! Messages with interpolated values can also be created with the [format_component!] macro:
+//! ```rust
+//! use typewheel::format_component;
+//!
+//! let component = format_component!("{player:gold} joined the game", player = "Notch");
+//! ```
+//!
 //! # Serializing Components
 //! Components can be serialized and deserialized using codecs. The most commonly used format for
 //! encoding components is JSON, which is implemented with the [codec::JsonComponentCodec] struct.
 //! Other serializers exist for a variety of formats and implementations.
 //!
+//! # Rendering Components
+//! Content that depends on who is viewing a component, such as scores or server-side translations,
+//! can be resolved with a [render::ComponentRenderer] before the component is serialized. Named
+//! placeholders such as `<player>` can be filled in with a [placeholder::PlaceholderReplacer].
+//! Messages that are sent often with different arguments can be precompiled into a
+//! [template::Template].
+//!
 //! # Crate Features
+//! * `graphemes`: Enables counting [text offsets][text::TextUnit] in grapheme clusters.
 //! * `json`: Enables the use of [codec::JsonComponentCodec] via the [serde_json] crate.
 //! * `nbt`: Enables setting NBT tags in item hovers.
+//! * `rayon`: Enables traversing, folding and encoding components [in parallel][par] via the
+//!   [rayon] crate.
+//! * `regex`: Enables searching for regular expressions with a [text::TextReplacer].
+//! * `resource-pack`: Enables loading custom [font metrics][font] from resource packs. Implies
+//!   `json`.
 //!
 //! [wiki]: https://wiki.vg/Chat
 //! [adventure]: https://docs.advntr.dev
@@ -44,10 +64,25 @@ pub mod codec;
 mod component;
 mod content;
 mod event;
+pub mod font;
+#[doc(hidden)]
+pub mod format;
+pub mod intern;
 pub mod iter;
 mod key;
+#[cfg(feature = "rayon")]
+pub mod par;
+pub mod path;
+pub mod placeholder;
+pub mod query;
+pub mod render;
 mod serial;
+pub mod shared;
+pub mod stats;
 mod style;
+pub mod template;
+pub mod text;
+pub mod visitor;
 
 pub use self::{
 	component::Component,
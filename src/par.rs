@@ -0,0 +1,298 @@
+//! A module for processing component trees in parallel with [rayon]. This module is only available
+//! with the `rayon` feature.
+//!
+//! Large trees can be traversed with [Component::par_iter()] and [Component::par_iter_mut()], and
+//! rebuilt with [fold_component()]. Batches of components can be folded with [fold_all()], and
+//! encoded or decoded with any [ComponentCodec] through [serialize_all()] and
+//! [deserialize_all()].
+//!
+//! Work is split between threads by subtree, so these are only worth using for trees with
+//! thousands of nodes, or for batches of many components.
+//!
+//! # Examples
+//! ```
+//! use rayon::prelude::*;
+//! use typewheel::{Component, TextColor};
+//! use typewheel::codec::PlainTextCodec;
+//! use typewheel::par;
+//!
+//! let mut component = Component::text("a").with_extra(["b", "c"]);
+//! component.par_iter_mut().for_each(|node| node.style.color = Some(TextColor::Red));
+//!
+//! assert!(component.par_iter().all(|node| node.style.color == Some(TextColor::Red)));
+//!
+//! let lines = par::serialize_all(PlainTextCodec, &[component, Component::text("d")]);
+//! assert_eq!(lines, ["abc", "d"]);
+//! ```
+
+use crate::codec::ComponentCodec;
+use crate::iter::{self, NodeMut};
+use crate::visitor::ComponentFolder;
+use crate::Component;
+use rayon::iter::plumbing::UnindexedConsumer;
+use rayon::prelude::*;
+
+impl Component {
+	/// Creates a parallel iterator over every node in this tree. Nodes are yielded in no particular
+	/// order. See the [ParIter] docs for more information.
+	#[inline]
+	pub fn par_iter(&self) -> ParIter<'_> {
+		ParIter {
+			root: self,
+			translate_args: false,
+			hover_text: false,
+		}
+	}
+
+	/// Creates a parallel iterator of mutable references to the content and style of every node in
+	/// this tree. This is the parallel equivalent of [Component::iter_mut()].
+	#[inline]
+	pub fn par_iter_mut(&mut self) -> ParIterMut<'_> {
+		ParIterMut { root: self }
+	}
+}
+
+/// A parallel iterator over the nodes of a [Component] tree. This is the parallel equivalent of a
+/// [FlatIterator][crate::iter::FlatIterator], and is created by [Component::par_iter()].
+///
+/// By default, only the tree formed by `extra` children is traversed. Translation arguments and
+/// hover text can be included with [Self::with_translate_args()] and [Self::with_hover_text()].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ParIter<'a> {
+	root: &'a Component,
+	translate_args: bool,
+	hover_text: bool,
+}
+
+impl ParIter<'_> {
+	/// Includes the arguments of [translation][crate::Content::Translation] nodes, and everything
+	/// nested in them.
+	pub fn with_translate_args(mut self) -> Self {
+		self.translate_args = true;
+		self
+	}
+
+	/// Includes the text of [ShowText][crate::HoverEvent::ShowText] hover events and the names of
+	/// [ShowEntity][crate::HoverEvent::ShowEntity] hover events, and everything nested in them.
+	pub fn with_hover_text(mut self) -> Self {
+		self.hover_text = true;
+		self
+	}
+}
+
+impl<'a> ParallelIterator for ParIter<'a> {
+	type Item = &'a Component;
+
+	fn drive_unindexed<C>(self, consumer: C) -> C::Result
+	where
+		C: UnindexedConsumer<Self::Item>,
+	{
+		let Self {
+			root,
+			translate_args,
+			hover_text,
+		} = self;
+
+		rayon::iter::walk_tree_prefix(root, move |node| {
+			iter::nested(node, translate_args, hover_text).map(|(_, child)| child)
+		})
+		.drive_unindexed(consumer)
+	}
+}
+
+impl<'a> IntoParallelIterator for &'a Component {
+	type Iter = ParIter<'a>;
+	type Item = &'a Component;
+
+	#[inline(always)]
+	fn into_par_iter(self) -> Self::Iter {
+		self.par_iter()
+	}
+}
+
+/// A parallel iterator over mutable references to the content and style of every node in a
+/// [Component] tree. This is the parallel equivalent of an [IterMut][crate::iter::IterMut], and is
+/// created by [Component::par_iter_mut()].
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ParIterMut<'a> {
+	root: &'a mut Component,
+}
+
+impl<'a> ParallelIterator for ParIterMut<'a> {
+	type Item = NodeMut<'a>;
+
+	fn drive_unindexed<C>(self, consumer: C) -> C::Result
+	where
+		C: UnindexedConsumer<Self::Item>,
+	{
+		let work = Work {
+			nodes: Vec::new(),
+			trees: vec![self.root],
+		};
+
+		rayon::iter::split(work, Work::split)
+			.flat_map_iter(Work::into_nodes)
+			.drive_unindexed(consumer)
+	}
+}
+
+impl<'a> IntoParallelIterator for &'a mut Component {
+	type Iter = ParIterMut<'a>;
+	type Item = NodeMut<'a>;
+
+	#[inline(always)]
+	fn into_par_iter(self) -> Self::Iter {
+		self.par_iter_mut()
+	}
+}
+
+/// A piece of a [ParIterMut]'s work: nodes that are ready to be yielded, and subtrees that have not
+/// been expanded yet.
+struct Work<'a> {
+	nodes: Vec<NodeMut<'a>>,
+	trees: Vec<&'a mut Component>,
+}
+
+impl<'a> Work<'a> {
+	/// Splits off half of the subtrees. Lone subtrees are expanded first, so that chains of single
+	/// children can still be split further down.
+	fn split(mut self) -> (Self, Option<Self>) {
+		while self.trees.len() == 1 {
+			let Component {
+				content,
+				style,
+				extra,
+			} = self.trees.pop().unwrap();
+
+			self.nodes.push(NodeMut { content, style });
+			self.trees.extend(extra.iter_mut());
+		}
+
+		if self.trees.len() < 2 {
+			return (self, None);
+		}
+
+		let trees = self.trees.split_off(self.trees.len() / 2);
+		let other = Self {
+			nodes: Vec::new(),
+			trees,
+		};
+
+		(self, Some(other))
+	}
+
+	/// Yields the rest of this work sequentially.
+	fn into_nodes(self) -> impl Iterator<Item = NodeMut<'a>> {
+		self.nodes
+			.into_iter()
+			.chain(self.trees.into_iter().flat_map(Component::iter_mut))
+	}
+}
+
+/// Folds a component tree in parallel. This is the parallel equivalent of
+/// [ComponentFolder::fold_component()].
+///
+/// The root's content and style are folded the same way as in the
+/// [default recursion][crate::visitor::fold_component()], and each of its children is then folded
+/// as a whole subtree by its own clone of `folder`, with [ComponentFolder::fold_component()]. The
+/// result is the same as folding the tree sequentially, as long as `folder` does not override
+/// [ComponentFolder::fold_component()] to change how the root itself is folded.
+///
+/// Work is split between the root's children, so this is only worth using for trees with many
+/// large top-level children.
+///
+/// # Examples
+/// ```
+/// use typewheel::{Component, Content};
+/// use typewheel::par;
+/// use typewheel::visitor::ComponentFolder;
+///
+/// #[derive(Clone)]
+/// struct Shout;
+///
+/// impl ComponentFolder for Shout {
+///     fn fold_text(&mut self, text: String) -> Content {
+///         Content::Text(text.to_uppercase())
+///     }
+/// }
+///
+/// let component = Component::text("hello, ").with_extra(["world"]);
+/// assert_eq!(
+///     par::fold_component(&Shout, component),
+///     Component::text("HELLO, ").with_extra(["WORLD"])
+/// );
+/// ```
+pub fn fold_component<F>(folder: &F, component: Component) -> Component
+where
+	F: ComponentFolder + Clone + Send + Sync,
+{
+	let Component {
+		content,
+		style,
+		extra,
+	} = component;
+
+	let mut root = folder.clone();
+	let style = root.fold_style(style);
+	let content = root.fold_content(content);
+	let extra = extra
+		.into_par_iter()
+		.map(|child| folder.clone().fold_component(child))
+		.collect();
+
+	Component::create(content, style, extra)
+}
+
+/// Folds a batch of components in parallel, returning them in the same order. Each component is
+/// folded sequentially by its own clone of `folder`, with [ComponentFolder::fold_component()].
+pub fn fold_all<F>(folder: &F, components: Vec<Component>) -> Vec<Component>
+where
+	F: ComponentFolder + Clone + Send + Sync,
+{
+	components
+		.into_par_iter()
+		.map(|component| folder.clone().fold_component(component))
+		.collect()
+}
+
+/// Serializes a batch of components in parallel, returning their encoded forms in the same order.
+///
+/// # Examples
+/// ```
+/// use typewheel::Component;
+/// use typewheel::codec::JsonCodec;
+/// use typewheel::par;
+///
+/// let components = [Component::text("a"), Component::translate("b", ["c"])];
+/// let encoded: Result<Vec<_>, _> = par::serialize_all(JsonCodec, &components).into_iter().collect();
+///
+/// assert_eq!(encoded.unwrap(), [r#""a""#, r#"{"translate":"b","with":["c"]}"#]);
+/// ```
+pub fn serialize_all<C>(codec: C, components: &[Component]) -> Vec<C::EncodeOutput>
+where
+	C: ComponentCodec + Send + Sync,
+	C::EncodeOutput: Send,
+{
+	components
+		.par_iter()
+		.map(|component| codec.serialize(component))
+		.collect()
+}
+
+/// Deserializes a batch of components in parallel, returning the results in the same order.
+pub fn deserialize_all<C, I>(
+	codec: C,
+	inputs: impl IntoParallelIterator<Item = I>,
+) -> Vec<C::DecodeOutput>
+where
+	C: ComponentCodec + Send + Sync,
+	C::DecodeOutput: Send,
+	I: Into<C::DecodeInput>,
+{
+	inputs
+		.into_par_iter()
+		.map(|input| codec.deserialize(input))
+		.collect()
+}
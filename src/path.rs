@@ -0,0 +1,562 @@
+//! A module for addressing nodes in component trees. A [ComponentPath] is a list of steps from a
+//! root component down to one of the nodes nested in it, and a [Cursor] moves around a tree one
+//! step at a time while keeping track of its path.
+//!
+//! Paths are written the same way that the nodes are nested in JSON: `extra[2]` is the third child
+//! of a node, `with[0]` is the first argument of a translation, `hover` is the text of a
+//! [ShowText][HoverEvent::ShowText] hover event and `hover.name` is the name of a
+//! [ShowEntity][HoverEvent::ShowEntity] hover event. Steps are separated by dots, and the empty
+//! path refers to the root.
+//!
+//! # Examples
+//! ```
+//! use typewheel::Component;
+//! use typewheel::path::ComponentPath;
+//!
+//! let component = Component::text("a").with_extra([
+//!     Component::text("b"),
+//!     Component::translate("c", [Component::text("d").with_extra(["e"])]),
+//! ]);
+//!
+//! let path: ComponentPath = "extra[1].with[0].extra[0]".parse().unwrap();
+//! assert_eq!(component.get(&path), Some(&Component::text("e")));
+//! assert_eq!(path.to_string(), "extra[1].with[0].extra[0]");
+//! ```
+
+use crate::iter::{count, nested};
+use crate::{Component, Content, HoverEvent};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A single step in a [ComponentPath].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum PathStep {
+	/// Steps into a child of a node, written as `extra[index]`.
+	Extra(usize),
+
+	/// Steps into an argument of a [translation][Content::Translation] node, written as
+	/// `with[index]`.
+	Arg(usize),
+
+	/// Steps into the text of a [ShowText][HoverEvent::ShowText] hover event, written as `hover`.
+	HoverText,
+
+	/// Steps into the name of a [ShowEntity][HoverEvent::ShowEntity] hover event, written as
+	/// `hover.name`.
+	EntityName,
+}
+
+impl PathStep {
+	/// Follows this step down from a node.
+	pub fn get(self, node: &Component) -> Option<&Component> {
+		match self {
+			Self::Extra(index) => node.extra.get(index),
+			Self::Arg(index) => match &node.content {
+				Content::Translation { with, .. } => with.get(index),
+				_ => None,
+			},
+			Self::HoverText => match &node.style.hover_event {
+				Some(HoverEvent::ShowText(text)) => Some(text),
+				_ => None,
+			},
+			Self::EntityName => match &node.style.hover_event {
+				Some(HoverEvent::ShowEntity(entity)) => Some(&entity.name),
+				_ => None,
+			},
+		}
+	}
+
+	/// Follows this step down from a node mutably.
+	pub fn get_mut(self, node: &mut Component) -> Option<&mut Component> {
+		match self {
+			Self::Extra(index) => node.extra.get_mut(index),
+			Self::Arg(index) => match &mut node.content {
+				Content::Translation { with, .. } => with.get_mut(index),
+				_ => None,
+			},
+			Self::HoverText => match &mut node.style.hover_event {
+				Some(HoverEvent::ShowText(text)) => Some(text),
+				_ => None,
+			},
+			Self::EntityName => match &mut node.style.hover_event {
+				Some(HoverEvent::ShowEntity(entity)) => Some(&mut entity.name),
+				_ => None,
+			},
+		}
+	}
+
+	/// Gets the same kind of step with another index. Steps without an index return [None].
+	fn with_index(self, index: usize) -> Option<Self> {
+		match self {
+			Self::Extra(_) => Some(Self::Extra(index)),
+			Self::Arg(_) => Some(Self::Arg(index)),
+			_ => None,
+		}
+	}
+}
+
+impl Display for PathStep {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Extra(index) => write!(f, "extra[{index}]"),
+			Self::Arg(index) => write!(f, "with[{index}]"),
+			Self::HoverText => f.write_str("hover"),
+			Self::EntityName => f.write_str("hover.name"),
+		}
+	}
+}
+
+/// A path from a root component to one of the nodes nested in it. See the [module docs][self] for
+/// the string format.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ComponentPath {
+	steps: Vec<PathStep>,
+}
+
+impl ComponentPath {
+	/// Creates a path to the root node.
+	#[inline]
+	pub const fn root() -> Self {
+		Self { steps: Vec::new() }
+	}
+
+	/// Gets the steps in this path.
+	#[inline]
+	pub fn steps(&self) -> &[PathStep] {
+		&self.steps
+	}
+
+	/// Checks if this path refers to the root node.
+	#[inline]
+	pub fn is_root(&self) -> bool {
+		self.steps.is_empty()
+	}
+
+	/// Gets the number of steps in this path, which is the depth of the node it refers to.
+	#[inline]
+	pub fn depth(&self) -> usize {
+		self.steps.len()
+	}
+
+	/// Gets the last step in this path.
+	#[inline]
+	pub fn last(&self) -> Option<PathStep> {
+		self.steps.last().copied()
+	}
+
+	/// Adds a step to the end of this path.
+	#[inline]
+	pub fn push(&mut self, step: PathStep) {
+		self.steps.push(step);
+	}
+
+	/// Removes the last step from this path and returns it.
+	#[inline]
+	pub fn pop(&mut self) -> Option<PathStep> {
+		self.steps.pop()
+	}
+
+	/// Gets the path to the parent of the node this path refers to.
+	pub fn parent(&self) -> Option<Self> {
+		let (_, steps) = self.steps.split_last()?;
+		Some(Self {
+			steps: steps.to_vec(),
+		})
+	}
+
+	/// A builder method for adding a step to the end of this path.
+	#[must_use]
+	pub fn join(mut self, step: PathStep) -> Self {
+		self.push(step);
+		self
+	}
+
+	/// A builder method for stepping into a child. Shorthand for `join(PathStep::Extra(index))`.
+	///
+	/// # Examples
+	/// ```
+	/// # use typewheel::path::ComponentPath;
+	/// let path = ComponentPath::root().child(2).arg(0);
+	/// assert_eq!(path.to_string(), "extra[2].with[0]");
+	/// ```
+	#[must_use]
+	#[inline]
+	pub fn child(self, index: usize) -> Self {
+		self.join(PathStep::Extra(index))
+	}
+
+	/// A builder method for stepping into a translation argument. Shorthand for
+	/// `join(PathStep::Arg(index))`.
+	#[must_use]
+	#[inline]
+	pub fn arg(self, index: usize) -> Self {
+		self.join(PathStep::Arg(index))
+	}
+}
+
+impl From<Vec<PathStep>> for ComponentPath {
+	#[inline]
+	fn from(steps: Vec<PathStep>) -> Self {
+		Self { steps }
+	}
+}
+
+impl FromIterator<PathStep> for ComponentPath {
+	fn from_iter<T: IntoIterator<Item = PathStep>>(iter: T) -> Self {
+		Self {
+			steps: iter.into_iter().collect(),
+		}
+	}
+}
+
+impl Display for ComponentPath {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		for (i, step) in self.steps.iter().enumerate() {
+			if i > 0 {
+				f.write_str(".")?;
+			}
+
+			step.fmt(f)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl FromStr for ComponentPath {
+	type Err = ParsePathError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut steps = Vec::new();
+		let mut rest = s;
+
+		while !rest.is_empty() {
+			let position = s.len() - rest.len();
+			let error = ParsePathError { position };
+
+			let step = if let Some(tail) = rest.strip_prefix("hover.name") {
+				rest = tail;
+				PathStep::EntityName
+			} else if let Some(tail) = rest.strip_prefix("hover") {
+				rest = tail;
+				PathStep::HoverText
+			} else {
+				let (kind, tail) = if let Some(tail) = rest.strip_prefix("extra[") {
+					(PathStep::Extra as fn(usize) -> PathStep, tail)
+				} else if let Some(tail) = rest.strip_prefix("with[") {
+					(PathStep::Arg as fn(usize) -> PathStep, tail)
+				} else {
+					return Err(error);
+				};
+
+				let (index, tail) = tail.split_once(']').ok_or(error)?;
+				rest = tail;
+				kind(parse_index(index).ok_or(error)?)
+			};
+
+			steps.push(step);
+
+			if let Some(tail) = rest.strip_prefix('.') {
+				if tail.is_empty() {
+					return Err(ParsePathError { position: s.len() });
+				}
+
+				rest = tail;
+			} else if !rest.is_empty() {
+				return Err(ParsePathError {
+					position: s.len() - rest.len(),
+				});
+			}
+		}
+
+		Ok(Self { steps })
+	}
+}
+
+/// Parses the index of a step. Only plain decimal digits are accepted, without a sign or leading
+/// zeros, so that every index has a single spelling.
+fn parse_index(index: &str) -> Option<usize> {
+	let canonical = index == "0" || !index.starts_with('0');
+	if index.is_empty() || !canonical || !index.bytes().all(|byte| byte.is_ascii_digit()) {
+		return None;
+	}
+
+	index.parse().ok()
+}
+
+/// An error returned when parsing an invalid [ComponentPath].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParsePathError {
+	/// The byte offset of the step that could not be parsed.
+	pub position: usize,
+}
+
+impl Display for ParsePathError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "invalid component path step at offset {}", self.position)
+	}
+}
+
+impl Error for ParsePathError {}
+
+impl Component {
+	/// Gets the node at a path. Returns [None] if the path does not point to a node.
+	pub fn get(&self, path: &ComponentPath) -> Option<&Component> {
+		path.steps
+			.iter()
+			.try_fold(self, |node, step| step.get(node))
+	}
+
+	/// Gets a mutable reference to the node at a path. Returns [None] if the path does not point to
+	/// a node.
+	pub fn get_mut(&mut self, path: &ComponentPath) -> Option<&mut Component> {
+		path.steps
+			.iter()
+			.try_fold(self, |node, step| step.get_mut(node))
+	}
+
+	/// Replaces the node at a path, returning the previous node. If the path does not point to a
+	/// node, nothing is replaced and [None] is returned.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::Component;
+	/// use typewheel::path::ComponentPath;
+	///
+	/// let mut component = Component::text("a").with_extra(["b"]);
+	/// let old = component.replace(&ComponentPath::root().child(0), Component::text("c"));
+	///
+	/// assert_eq!(old, Some(Component::text("b")));
+	/// assert_eq!(component, Component::text("a").with_extra(["c"]));
+	/// ```
+	pub fn replace(&mut self, path: &ComponentPath, replacement: Component) -> Option<Component> {
+		let node = self.get_mut(path)?;
+		Some(std::mem::replace(node, replacement))
+	}
+
+	/// Creates a [Cursor] pointing at this component.
+	#[inline]
+	pub fn cursor(&self) -> Cursor<'_> {
+		Cursor::new(self)
+	}
+
+	/// Creates a depth-first iterator that yields every node along with its path. See the
+	/// [PathIterator] docs for more information.
+	#[inline]
+	pub fn iter_paths(&self) -> PathIterator<'_> {
+		PathIterator::new(self)
+	}
+}
+
+/// A cursor that points at a node in a component tree, and can move between its parent, siblings
+/// and children. Moves return `false` and leave the cursor in place if there is no node to move to.
+///
+/// # Examples
+/// ```
+/// use typewheel::Component;
+///
+/// let component = Component::translate("a", [Component::text("b")])
+///     .with_extra(["c", "d"]);
+///
+/// let mut cursor = component.cursor();
+/// assert!(cursor.first_child());
+/// assert!(cursor.next_sibling());
+/// assert_eq!(cursor.node(), &Component::text("d"));
+/// assert_eq!(cursor.path().to_string(), "extra[1]");
+///
+/// assert!(!cursor.next_sibling());
+/// assert!(cursor.parent());
+/// assert!(cursor.into_translation_arg(0));
+/// assert_eq!(cursor.node(), &Component::text("b"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cursor<'a> {
+	/// Every node from the root to the current node, inclusive.
+	nodes: Vec<&'a Component>,
+	path: ComponentPath,
+}
+
+impl<'a> Cursor<'a> {
+	/// Creates a cursor pointing at a root node.
+	pub fn new(root: &'a Component) -> Self {
+		Self {
+			nodes: vec![root],
+			path: ComponentPath::root(),
+		}
+	}
+
+	/// Gets the node that this cursor points at.
+	#[inline]
+	pub fn node(&self) -> &'a Component {
+		self.nodes.last().expect("cursors always point at a node")
+	}
+
+	/// Gets the path from the root to the node that this cursor points at.
+	#[inline]
+	pub fn path(&self) -> &ComponentPath {
+		&self.path
+	}
+
+	/// Moves to the node at the end of a step from the current node.
+	pub fn step(&mut self, step: PathStep) -> bool {
+		let Some(next) = step.get(self.node()) else {
+			return false;
+		};
+
+		self.nodes.push(next);
+		self.path.push(step);
+		true
+	}
+
+	/// Moves to the parent of the current node.
+	pub fn parent(&mut self) -> bool {
+		if self.path.pop().is_none() {
+			return false;
+		}
+
+		self.nodes.pop();
+		true
+	}
+
+	/// Moves to the first child of the current node.
+	#[inline]
+	pub fn first_child(&mut self) -> bool {
+		self.step(PathStep::Extra(0))
+	}
+
+	/// Moves to a child of the current node.
+	#[inline]
+	pub fn child(&mut self, index: usize) -> bool {
+		self.step(PathStep::Extra(index))
+	}
+
+	/// Moves to a translation argument of the current node.
+	#[inline]
+	pub fn into_translation_arg(&mut self, index: usize) -> bool {
+		self.step(PathStep::Arg(index))
+	}
+
+	/// Moves to the hover text or the hovered entity's name of the current node.
+	pub fn into_hover(&mut self) -> bool {
+		self.step(PathStep::HoverText) || self.step(PathStep::EntityName)
+	}
+
+	/// Moves to the next sibling of the current node. Siblings are the other children or
+	/// translation arguments of the same parent.
+	pub fn next_sibling(&mut self) -> bool {
+		self.sibling(|index| index.checked_add(1))
+	}
+
+	/// Moves to the previous sibling of the current node.
+	pub fn prev_sibling(&mut self) -> bool {
+		self.sibling(|index| index.checked_sub(1))
+	}
+
+	fn sibling(&mut self, offset: impl FnOnce(usize) -> Option<usize>) -> bool {
+		let (Some(step), [.., parent, _]) = (self.path.last(), self.nodes.as_slice()) else {
+			return false;
+		};
+
+		let index = match step {
+			PathStep::Extra(index) | PathStep::Arg(index) => index,
+			_ => return false,
+		};
+
+		let Some(step) = offset(index).and_then(|index| step.with_index(index)) else {
+			return false;
+		};
+
+		let Some(next) = step.get(parent) else {
+			return false;
+		};
+
+		*self
+			.nodes
+			.last_mut()
+			.expect("cursors always point at a node") = next;
+		self.path.pop();
+		self.path.push(step);
+		true
+	}
+}
+
+/// A depth-first iterator that yields every node in a tree along with its [ComponentPath]. Like a
+/// [FlatIterator][crate::iter::FlatIterator], it can optionally descend into translation arguments
+/// and hover text.
+///
+/// # Examples
+/// ```
+/// use typewheel::Component;
+///
+/// let component = Component::text("a").with_extra([Component::text("b").with_extra(["c"])]);
+/// let paths: Vec<_> = component
+///     .iter_paths()
+///     .map(|(path, _)| path.to_string())
+///     .collect();
+///
+/// assert_eq!(paths, ["", "extra[0]", "extra[0].extra[0]"]);
+/// ```
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PathIterator<'a> {
+	stack: Vec<(ComponentPath, &'a Component)>,
+	include_translate_args: bool,
+	include_hover_text: bool,
+}
+
+impl<'a> PathIterator<'a> {
+	fn new(root: &'a Component) -> Self {
+		Self {
+			stack: vec![(ComponentPath::root(), root)],
+			include_translate_args: false,
+			include_hover_text: false,
+		}
+	}
+
+	/// Sets a flag to include translation arguments in the output of this iterator.
+	pub fn with_translate_args(mut self) -> Self {
+		self.include_translate_args = true;
+		self
+	}
+
+	/// Sets a flag to include hover text and hovered entity names in the output of this iterator.
+	pub fn with_hover_text(mut self) -> Self {
+		self.include_hover_text = true;
+		self
+	}
+}
+
+impl<'a> Iterator for PathIterator<'a> {
+	type Item = (ComponentPath, &'a Component);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let (path, node) = self.stack.pop()?;
+
+		let nested = nested(node, self.include_translate_args, self.include_hover_text);
+
+		// Pushed in reverse, so that they are popped in the order that they are serialized.
+		for (step, child) in nested.rev() {
+			self.stack.push((path.clone().join(step), child));
+		}
+
+		Some((path, node))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let (args, hover) = (self.include_translate_args, self.include_hover_text);
+		let remaining = self
+			.stack
+			.iter()
+			.map(|(_, node)| count(node, args, hover))
+			.sum();
+
+		(remaining, Some(remaining))
+	}
+}
+
+impl ExactSizeIterator for PathIterator<'_> {}
+
+impl std::iter::FusedIterator for PathIterator<'_> {}
@@ -0,0 +1,290 @@
+//! A module for replacing named placeholders in component text. Placeholders are written as
+//! `<name>`, where the name is made up of ASCII letters, digits, and the characters `_`, `-`, `.`
+//! and `:`.
+//!
+//! Placeholders are searched for in the text of a component tree as it would be displayed, so a
+//! placeholder can start in one text node and end in one of its siblings. Replacement values are
+//! inserted into the node that the placeholder starts in, which means that they inherit the style
+//! surrounding the placeholder.
+//!
+//! # Examples
+//! ```
+//! use std::collections::HashMap;
+//! use typewheel::{Component, Style, TextColor};
+//! use typewheel::placeholder::{Placeholder, PlaceholderReplacer};
+//!
+//! let message = Component::text("<player> joined with <count> friends")
+//!     .with_color(TextColor::Yellow);
+//!
+//! let values = HashMap::from([
+//!     ("player", Placeholder::from(Component::text("Notch").with_bold(true))),
+//!     ("count", Placeholder::from("3")),
+//! ]);
+//!
+//! let replaced = PlaceholderReplacer::new(values).replace(message);
+//! assert_eq!(
+//!     replaced,
+//!     Component::empty()
+//!         .with_color(TextColor::Yellow)
+//!         .with_extra([
+//!             Component::text("Notch").with_bold(true),
+//!             Component::text(" joined with 3 friends"),
+//!         ])
+//! );
+//! ```
+
+use crate::render::ComponentRenderer;
+use crate::text::flow::{replace_ranges, Flow};
+use crate::{Component, Style};
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+use std::ops::Range;
+
+/// A value that a placeholder is replaced with.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Placeholder {
+	/// Replaces the placeholder with a component. The component is inserted as a child of the node
+	/// containing the placeholder, so it inherits that node's style.
+	Component(Component),
+
+	/// Replaces the placeholder with plain text. The text is merged into the surrounding text node.
+	Text(String),
+
+	/// Removes the placeholder and applies a style to all content following it in the node that
+	/// contains it, including that node's children.
+	Style(Style),
+}
+
+impl From<Component> for Placeholder {
+	#[inline(always)]
+	fn from(value: Component) -> Self {
+		Self::Component(value)
+	}
+}
+
+impl From<String> for Placeholder {
+	#[inline(always)]
+	fn from(value: String) -> Self {
+		Self::Text(value)
+	}
+}
+
+impl From<&str> for Placeholder {
+	#[inline(always)]
+	fn from(value: &str) -> Self {
+		Self::Text(value.to_owned())
+	}
+}
+
+impl From<Style> for Placeholder {
+	#[inline(always)]
+	fn from(value: Style) -> Self {
+		Self::Style(value)
+	}
+}
+
+/// A source of placeholder values. Resolvers are implemented for maps from names to values and
+/// for closures accepting a name, and can be chained together with [PlaceholderResolver::or()].
+pub trait PlaceholderResolver {
+	/// Resolves a placeholder by name, returning [None] if this resolver does not know it.
+	fn resolve(&self, name: &str) -> Option<Placeholder>;
+
+	/// Chains this resolver with another one. The other resolver is only queried for names that
+	/// this resolver does not know.
+	///
+	/// # Examples
+	/// ```
+	/// # use std::collections::HashMap;
+	/// use typewheel::placeholder::{Placeholder, PlaceholderResolver};
+	///
+	/// let resolver = HashMap::from([("a", "1")])
+	///     .or(|name: &str| (name == "b").then(|| Placeholder::from("2")));
+	///
+	/// assert_eq!(resolver.resolve("a"), Some(Placeholder::from("1")));
+	/// assert_eq!(resolver.resolve("b"), Some(Placeholder::from("2")));
+	/// assert_eq!(resolver.resolve("c"), None);
+	/// ```
+	fn or<R: PlaceholderResolver>(self, other: R) -> Or<Self, R>
+	where
+		Self: Sized,
+	{
+		Or(self, other)
+	}
+}
+
+/// A [PlaceholderResolver] that tries two resolvers in order. See [PlaceholderResolver::or()].
+#[derive(Clone, Debug)]
+pub struct Or<A, B>(A, B);
+
+impl<A: PlaceholderResolver, B: PlaceholderResolver> PlaceholderResolver for Or<A, B> {
+	fn resolve(&self, name: &str) -> Option<Placeholder> {
+		self.0.resolve(name).or_else(|| self.1.resolve(name))
+	}
+}
+
+impl<F> PlaceholderResolver for F
+where
+	F: Fn(&str) -> Option<Placeholder>,
+{
+	#[inline(always)]
+	fn resolve(&self, name: &str) -> Option<Placeholder> {
+		self(name)
+	}
+}
+
+impl<K, V, S> PlaceholderResolver for HashMap<K, V, S>
+where
+	K: Borrow<str> + Hash + Eq,
+	V: Clone + Into<Placeholder>,
+	S: BuildHasher,
+{
+	fn resolve(&self, name: &str) -> Option<Placeholder> {
+		self.get(name).cloned().map(Into::into)
+	}
+}
+
+impl<K, V> PlaceholderResolver for BTreeMap<K, V>
+where
+	K: Borrow<str> + Ord,
+	V: Clone + Into<Placeholder>,
+{
+	fn resolve(&self, name: &str) -> Option<Placeholder> {
+		self.get(name).cloned().map(Into::into)
+	}
+}
+
+/// Determines what happens to placeholders that no resolver knows.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UnknownPlaceholder {
+	/// Leaves unknown placeholders in the text as they were written. This is the default.
+	#[default]
+	Keep,
+
+	/// Removes unknown placeholders from the text.
+	Remove,
+
+	/// Replaces unknown placeholders with a fixed value.
+	Replace(Box<Placeholder>),
+}
+
+impl UnknownPlaceholder {
+	/// Creates a policy that replaces unknown placeholders with a fixed value.
+	#[inline]
+	pub fn replace(value: impl Into<Placeholder>) -> Self {
+		Self::Replace(Box::new(value.into()))
+	}
+}
+
+/// Replaces placeholders in component trees using a [PlaceholderResolver]. See the [module
+/// docs][self] for more information.
+///
+/// Placeholders are replaced in a component's children, in the arguments of translation
+/// components, and in hover text. Translation arguments and hover text are treated as separate
+/// pieces of text, so a placeholder cannot span across them.
+///
+/// This type also implements [ComponentRenderer], so it can be used as a stage in a [render
+/// pipeline][crate::render::RenderPipeline].
+#[derive(Clone, Debug)]
+pub struct PlaceholderReplacer<R> {
+	resolver: R,
+	unknown: UnknownPlaceholder,
+}
+
+impl<R: PlaceholderResolver> PlaceholderReplacer<R> {
+	/// Creates a new replacer that looks up placeholder values with the provided resolver.
+	#[inline]
+	pub fn new(resolver: R) -> Self {
+		Self {
+			resolver,
+			unknown: UnknownPlaceholder::default(),
+		}
+	}
+
+	/// A builder method for setting the [policy][UnknownPlaceholder] for placeholders that the
+	/// resolver does not know. This method assumes ownership of `self`, and passes it back when it
+	/// returns.
+	///
+	/// # Examples
+	/// ```
+	/// # use std::collections::HashMap;
+	/// use typewheel::Component;
+	/// use typewheel::placeholder::{PlaceholderReplacer, UnknownPlaceholder};
+	///
+	/// let replacer = PlaceholderReplacer::new(HashMap::<String, String>::new())
+	///     .with_unknown(UnknownPlaceholder::Remove);
+	///
+	/// assert_eq!(replacer.replace(Component::text("a<b>c")), Component::text("ac"));
+	/// ```
+	#[must_use]
+	pub fn with_unknown(mut self, policy: UnknownPlaceholder) -> Self {
+		self.unknown = policy;
+		self
+	}
+
+	/// Replaces all placeholders in a component tree.
+	pub fn replace(&self, component: Component) -> Component {
+		replace_ranges(component, false, &mut |flow: &Flow| {
+			find_placeholders(&flow.text)
+				.filter_map(|(range, name)| Some((range, self.resolve(name)?)))
+				.collect()
+		})
+	}
+
+	/// Resolves a placeholder name according to the resolver and unknown policy. [None] means
+	/// that the placeholder is left untouched.
+	fn resolve(&self, name: &str) -> Option<Placeholder> {
+		self.resolver.resolve(name).or_else(|| match &self.unknown {
+			UnknownPlaceholder::Keep => None,
+			UnknownPlaceholder::Remove => Some(Placeholder::Text(String::new())),
+			UnknownPlaceholder::Replace(value) => Some(Placeholder::clone(value)),
+		})
+	}
+}
+
+impl<Ctx: ?Sized, R: PlaceholderResolver> ComponentRenderer<Ctx> for PlaceholderReplacer<R> {
+	fn render(&self, component: &Component, _ctx: &Ctx) -> Component {
+		self.replace(component.clone())
+	}
+}
+
+/// Finds every placeholder in a string, returning the byte range of each placeholder (including
+/// its brackets) and its name.
+fn find_placeholders(text: &str) -> impl Iterator<Item = (Range<usize>, &str)> {
+	let mut offset = 0;
+
+	std::iter::from_fn(move || loop {
+		let start = offset + text[offset..].find('<')?;
+		let name_start = start + 1;
+		let name_len = text[name_start..]
+			.find(|c: char| !is_name_char(c))
+			.unwrap_or(text.len() - name_start);
+		let name_end = name_start + name_len;
+
+		if name_len > 0 && text[name_end..].starts_with('>') {
+			offset = name_end + 1;
+			return Some((start..offset, &text[name_start..name_end]));
+		}
+
+		offset = name_start;
+	})
+}
+
+/// Checks if a character is valid in a placeholder name.
+#[inline]
+fn is_name_char(c: char) -> bool {
+	c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':')
+}
+
+#[cfg(test)]
+mod tests {
+	use super::find_placeholders;
+
+	#[test]
+	fn scanning() {
+		let found: Vec<_> = find_placeholders("<a> << b> <c.d><e").collect();
+		assert_eq!(found, [(0..3, "a"), (10..15, "c.d")]);
+	}
+}
@@ -0,0 +1,606 @@
+//! A module for finding nodes in component trees with CSS-like selectors.
+//!
+//! A [Query] is parsed from a string, and is evaluated against a component with
+//! [Component::query()]. Queries look at every node in a tree, including translation arguments,
+//! hover text and hovered entity names, and yield each matching node along with its
+//! [path][ComponentPath].
+//!
+//! # Syntax
+//! A query is made of one or more selectors separated by commas, and matches nodes that match any
+//! of them. Each selector is a chain of compound selectors joined by combinators:
+//!
+//! | Syntax   | Matches                                                            |
+//! | -------- | ------------------------------------------------------------------ |
+//! | `a b`    | Nodes matching `b` that are nested anywhere inside a node matching `a` |
+//! | `a > b`  | Nodes matching `b` that are nested directly inside a node matching `a` |
+//!
+//! A compound selector starts with an optional node type, followed by any number of attribute
+//! tests in square brackets. The node types are `text`, `translate`, `keybind`, `score`, `empty`
+//! and `virtual`, which match content types, and `with` and `hover`, which match translation
+//! arguments and hover text (or hovered entity names) respectively. `*` matches any node.
+//!
+//! | Attribute                                                      | Tests                          |
+//! | -------------------------------------------------------------- | ------------------------------ |
+//! | `bold`, `italic`, `underlined`, `strikethrough`, `obfuscated` | The formatting flag. `[bold]` is short for `[bold=true]`. |
+//! | `color`                                                        | The color, by name or as `#RRGGBB`. Supports `=` and `!=`. |
+//! | `text`                                                         | The text of a text node.       |
+//! | `key`                                                          | The key of a translation or keybind node. |
+//! | `name`, `objective`, `value`                                   | The fields of a score node.    |
+//! | `font`, `insertion`                                            | The style field of the same name. |
+//! | `click`, `hover`                                               | The event's action, such as `run_command` or `show_text`. |
+//!
+//! Style attributes test a node's *effective* style, including the style it inherits from its
+//! parents. Other attributes can be tested for presence (`[font]`), or compared with `=`
+//! (equals), `!=` (does not equal), `^=` (starts with), `$=` (ends with) or `*=` (contains).
+//! Values can be bare words or quoted strings.
+//!
+//! # Examples
+//! ```
+//! use typewheel::{ClickEvent, Component, TextColor};
+//! use typewheel::query::Query;
+//!
+//! let component = Component::text("Welcome! ")
+//!     .with_color(TextColor::Red)
+//!     .with_extra([
+//!         Component::text("Spawn").with_bold(true).with_click_event(ClickEvent::run_command("/spawn")),
+//!         Component::translate("chat.type.text", ["Notch", "hi"]),
+//!     ]);
+//!
+//! let query: Query = "text[bold][color=red]".parse().unwrap();
+//! let paths: Vec<_> = component.query(&query).map(|(path, _)| path.to_string()).collect();
+//! assert_eq!(paths, ["extra[0]"]);
+//!
+//! let query: Query = r#"translate[key^="chat."] > with"#.parse().unwrap();
+//! let args: Vec<_> = component.query(&query).map(|(_, node)| node).collect();
+//! assert_eq!(args, [&Component::text("Notch"), &Component::text("hi")]);
+//! ```
+
+use crate::iter::{Tagged, Visit, VisitingIterator};
+use crate::path::{ComponentPath, PathStep};
+use crate::{ClickEvent, Component, Content, HoverEvent, Style, TextColor};
+use serde::de::IntoDeserializer;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::iter::FusedIterator;
+use std::str::FromStr;
+
+/// A parsed selector query. See the [module docs][self] for the syntax.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Query {
+	selectors: Vec<Selector>,
+}
+
+/// A chain of compound selectors. Each compound is stored with the combinator that joins it to the
+/// previous one; the first combinator is unused.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Selector {
+	parts: Vec<(Combinator, Compound)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Combinator {
+	Descendant,
+	Child,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Compound {
+	kind: Option<Kind>,
+	tests: Vec<Test>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+	Text,
+	Translate,
+	Keybind,
+	Score,
+	Empty,
+	Virtual,
+	Arg,
+	Hover,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Test {
+	Flag(Flag, bool),
+	Color(TextColor, bool),
+	Attr(Attr, Option<(Op, String)>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Flag {
+	Bold,
+	Italic,
+	Underlined,
+	Strikethrough,
+	Obfuscated,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Attr {
+	Text,
+	Key,
+	Name,
+	Objective,
+	Value,
+	Font,
+	Insertion,
+	Click,
+	Hover,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+	Equals,
+	NotEquals,
+	Prefix,
+	Suffix,
+	Contains,
+}
+
+/// A node being matched, along with the context that selectors can test.
+#[derive(Clone, Debug)]
+struct Frame<'a> {
+	role: Option<PathStep>,
+	node: &'a Component,
+	style: Style,
+}
+
+impl Selector {
+	fn matches(&self, stack: &[Frame]) -> bool {
+		matches_parts(&self.parts, stack)
+	}
+}
+
+/// Checks if the last frame in a stack matches the last compound, and its ancestors match the rest.
+fn matches_parts(parts: &[(Combinator, Compound)], stack: &[Frame]) -> bool {
+	let (Some(((combinator, last), rest)), Some((frame, ancestors))) =
+		(parts.split_last(), stack.split_last())
+	else {
+		return false;
+	};
+
+	if !last.matches(frame) {
+		return false;
+	}
+
+	if rest.is_empty() {
+		return true;
+	}
+
+	match combinator {
+		Combinator::Child => matches_parts(rest, ancestors),
+		Combinator::Descendant => {
+			(1..=ancestors.len()).any(|len| matches_parts(rest, &ancestors[..len]))
+		}
+	}
+}
+
+impl Compound {
+	fn matches(&self, frame: &Frame) -> bool {
+		self.kind.is_none_or(|kind| kind.matches(frame))
+			&& self.tests.iter().all(|test| test.matches(frame))
+	}
+}
+
+impl Kind {
+	fn matches(self, frame: &Frame) -> bool {
+		match (self, &frame.node.content) {
+			(Self::Text, Content::Text(_))
+			| (Self::Translate, Content::Translation { .. })
+			| (Self::Keybind, Content::Keybind(_))
+			| (Self::Score, Content::Score { .. })
+			| (Self::Empty, Content::Empty)
+			| (Self::Virtual, Content::Virtual(_)) => true,
+			(Self::Arg, _) => matches!(frame.role, Some(PathStep::Arg(_))),
+			(Self::Hover, _) => {
+				matches!(frame.role, Some(PathStep::HoverText | PathStep::EntityName))
+			}
+			_ => false,
+		}
+	}
+}
+
+impl Test {
+	fn matches(&self, frame: &Frame) -> bool {
+		match self {
+			Self::Flag(flag, expected) => flag.get(&frame.style).unwrap_or(false) == *expected,
+			Self::Color(color, equal) => (frame.style.color == Some(*color)) == *equal,
+			Self::Attr(attr, None) => attr.get(frame).is_some(),
+			Self::Attr(attr, Some((op, expected))) => {
+				let value = attr.get(frame);
+				match (op, value) {
+					(Op::NotEquals, value) => value != Some(expected),
+					(_, None) => false,
+					(Op::Equals, Some(value)) => value == expected,
+					(Op::Prefix, Some(value)) => value.starts_with(expected.as_str()),
+					(Op::Suffix, Some(value)) => value.ends_with(expected.as_str()),
+					(Op::Contains, Some(value)) => value.contains(expected.as_str()),
+				}
+			}
+		}
+	}
+}
+
+impl Flag {
+	fn get(self, style: &Style) -> Option<bool> {
+		match self {
+			Self::Bold => style.bold,
+			Self::Italic => style.italic,
+			Self::Underlined => style.underlined,
+			Self::Strikethrough => style.strikethrough,
+			Self::Obfuscated => style.obfuscated,
+		}
+	}
+}
+
+impl Attr {
+	/// Gets the value of this attribute for a node. Style attributes are read from the node's
+	/// effective style, so that they are inherited like flags and colors.
+	fn get<'f>(self, frame: &'f Frame) -> Option<&'f str> {
+		let style = &frame.style;
+		match (self, &frame.node.content) {
+			(Self::Text, Content::Text(text)) => Some(text),
+			(Self::Key, Content::Translation { key, .. } | Content::Keybind(key)) => Some(key),
+			(Self::Name, Content::Score { name, .. }) => Some(name),
+			(Self::Objective, Content::Score { objective, .. }) => Some(objective),
+			(Self::Value, Content::Score { value, .. }) => Some(value),
+			(Self::Font, _) => style.font.as_deref(),
+			(Self::Insertion, _) => style.insertion.as_deref(),
+			(Self::Click, _) => style.click_event.as_ref().map(|event| match event {
+				ClickEvent::RunCommand(_) => "run_command",
+				ClickEvent::SuggestCommand(_) => "suggest_command",
+				ClickEvent::ChangePage(_) => "change_page",
+				ClickEvent::Copy(_) => "copy_to_clipboard",
+			}),
+			(Self::Hover, _) => style.hover_event.as_ref().map(|event| match event {
+				HoverEvent::ShowText(_) => "show_text",
+				HoverEvent::ShowItem(_) => "show_item",
+				HoverEvent::ShowEntity(_) => "show_entity",
+			}),
+			_ => None,
+		}
+	}
+}
+
+impl FromStr for Query {
+	type Err = ParseQueryError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut parser = Parser { input: s, pos: 0 };
+		let mut selectors = vec![parser.selector()?];
+
+		while parser.eat(",") {
+			selectors.push(parser.selector()?);
+		}
+
+		parser.skip_whitespace();
+		if parser.pos < s.len() {
+			return Err(parser.error());
+		}
+
+		Ok(Self { selectors })
+	}
+}
+
+/// A recursive-descent parser over a query string.
+struct Parser<'a> {
+	input: &'a str,
+	pos: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn error(&self) -> ParseQueryError {
+		ParseQueryError { position: self.pos }
+	}
+
+	fn rest(&self) -> &'a str {
+		&self.input[self.pos..]
+	}
+
+	/// Skips whitespace, returning whether there was any.
+	fn skip_whitespace(&mut self) -> bool {
+		let rest = self.rest();
+		let trimmed = rest.trim_start();
+		self.pos += rest.len() - trimmed.len();
+		rest.len() != trimmed.len()
+	}
+
+	/// Consumes a token, along with any whitespace around it.
+	fn eat(&mut self, token: &str) -> bool {
+		let start = self.pos;
+		self.skip_whitespace();
+
+		if self.rest().starts_with(token) {
+			self.pos += token.len();
+			self.skip_whitespace();
+			true
+		} else {
+			self.pos = start;
+			false
+		}
+	}
+
+	/// Consumes a word made of letters, digits, and `_`, `-`, `.`, `:` or `#`.
+	fn word(&mut self) -> &'a str {
+		let rest = self.rest();
+		let len = rest
+			.find(|c: char| !(c.is_alphanumeric() || "_-.:#".contains(c)))
+			.unwrap_or(rest.len());
+
+		self.pos += len;
+		&rest[..len]
+	}
+
+	fn selector(&mut self) -> Result<Selector, ParseQueryError> {
+		self.skip_whitespace();
+		let mut parts = vec![(Combinator::Descendant, self.compound()?)];
+
+		loop {
+			let start = self.pos;
+			let spaced = self.skip_whitespace();
+			if self.rest().is_empty() || self.rest().starts_with(',') {
+				self.pos = start;
+				break;
+			}
+
+			let combinator = if self.eat(">") {
+				Combinator::Child
+			} else if spaced {
+				Combinator::Descendant
+			} else {
+				return Err(self.error());
+			};
+
+			parts.push((combinator, self.compound()?));
+		}
+
+		Ok(Selector { parts })
+	}
+
+	fn compound(&mut self) -> Result<Compound, ParseQueryError> {
+		let start = self.pos;
+
+		let kind = if self.rest().starts_with('*') {
+			self.pos += 1;
+			None
+		} else {
+			match self.word() {
+				"" => None,
+				"text" => Some(Kind::Text),
+				"translate" => Some(Kind::Translate),
+				"keybind" => Some(Kind::Keybind),
+				"score" => Some(Kind::Score),
+				"empty" => Some(Kind::Empty),
+				"virtual" => Some(Kind::Virtual),
+				"with" => Some(Kind::Arg),
+				"hover" => Some(Kind::Hover),
+				_ => return Err(ParseQueryError { position: start }),
+			}
+		};
+
+		let mut tests = Vec::new();
+		while self.rest().starts_with('[') {
+			self.pos += 1;
+			tests.push(self.test()?);
+		}
+
+		if self.pos == start {
+			return Err(self.error());
+		}
+
+		Ok(Compound { kind, tests })
+	}
+
+	fn test(&mut self) -> Result<Test, ParseQueryError> {
+		let start = self.pos;
+		let name = self.word();
+
+		let op = [
+			("!=", Op::NotEquals),
+			("^=", Op::Prefix),
+			("$=", Op::Suffix),
+			("*=", Op::Contains),
+			("=", Op::Equals),
+		]
+		.into_iter()
+		.find(|(token, _)| self.rest().starts_with(token));
+
+		let comparison = match op {
+			Some((token, op)) => {
+				self.pos += token.len();
+				Some((op, self.value()?))
+			}
+			None => None,
+		};
+
+		if !self.rest().starts_with(']') {
+			return Err(self.error());
+		}
+
+		self.pos += 1;
+		let invalid = ParseQueryError { position: start };
+
+		let flag = match name {
+			"bold" => Some(Flag::Bold),
+			"italic" => Some(Flag::Italic),
+			"underlined" => Some(Flag::Underlined),
+			"strikethrough" => Some(Flag::Strikethrough),
+			"obfuscated" => Some(Flag::Obfuscated),
+			_ => None,
+		};
+
+		if let Some(flag) = flag {
+			let expected = match comparison.as_ref().map(|(op, value)| (op, value.as_str())) {
+				None => true,
+				Some((Op::Equals, "true")) => true,
+				Some((Op::Equals, "false")) => false,
+				_ => return Err(invalid),
+			};
+
+			return Ok(Test::Flag(flag, expected));
+		}
+
+		if name == "color" {
+			let (equal, value) = match comparison {
+				Some((Op::Equals, value)) => (true, value),
+				Some((Op::NotEquals, value)) => (false, value),
+				_ => return Err(invalid),
+			};
+
+			return parse_color(&value)
+				.map(|color| Test::Color(color, equal))
+				.ok_or(invalid);
+		}
+
+		let attr = match name {
+			"text" => Attr::Text,
+			"key" => Attr::Key,
+			"name" => Attr::Name,
+			"objective" => Attr::Objective,
+			"value" => Attr::Value,
+			"font" => Attr::Font,
+			"insertion" => Attr::Insertion,
+			"click" => Attr::Click,
+			"hover" => Attr::Hover,
+			_ => return Err(invalid),
+		};
+
+		Ok(Test::Attr(attr, comparison))
+	}
+
+	/// Parses a bare word or a quoted string. Quoted strings can escape quotes and backslashes with
+	/// a backslash.
+	fn value(&mut self) -> Result<String, ParseQueryError> {
+		let Some(quoted) = self.rest().strip_prefix('"') else {
+			return match self.word() {
+				"" => Err(self.error()),
+				word => Ok(word.to_owned()),
+			};
+		};
+
+		let mut value = String::new();
+		let mut chars = quoted.char_indices();
+
+		while let Some((i, c)) = chars.next() {
+			match c {
+				'"' => {
+					self.pos += i + 2;
+					return Ok(value);
+				}
+				'\\' => match chars.next() {
+					Some((_, escaped)) => value.push(escaped),
+					None => break,
+				},
+				c => value.push(c),
+			}
+		}
+
+		self.pos = self.input.len();
+		Err(self.error())
+	}
+}
+
+/// Parses a named color or a `#RRGGBB` hex color.
+fn parse_color(value: &str) -> Option<TextColor> {
+	if let Some(hex) = value.strip_prefix('#') {
+		return u32::from_str_radix(hex, 16).ok().map(TextColor::Hex);
+	}
+
+	let deserializer: serde::de::value::StrDeserializer<serde::de::value::Error> =
+		value.into_deserializer();
+	TextColor::deserialize(deserializer).ok()
+}
+
+/// An error returned when parsing an invalid [Query].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseQueryError {
+	/// The byte offset in the query where parsing failed.
+	pub position: usize,
+}
+
+impl Display for ParseQueryError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "invalid query at offset {}", self.position)
+	}
+}
+
+impl Error for ParseQueryError {}
+
+impl Component {
+	/// Finds every node in this tree that matches a query, along with its path. Nodes are yielded
+	/// in the order that they are serialized. See the [query module][crate::query] for more
+	/// information.
+	pub fn query<'a>(&'a self, query: &'a Query) -> Matches<'a> {
+		Matches {
+			query,
+			iter: self
+				.visit()
+				.with_translate_args()
+				.with_hover_text()
+				.tagged(),
+			stack: Vec::new(),
+			path: ComponentPath::root(),
+		}
+	}
+}
+
+/// An iterator over the nodes that match a [Query]. Created by [Component::query()].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Matches<'a> {
+	query: &'a Query,
+	iter: Tagged<VisitingIterator<'a>>,
+	stack: Vec<Frame<'a>>,
+	path: ComponentPath,
+}
+
+impl<'a> Iterator for Matches<'a> {
+	type Item = (ComponentPath, &'a Component);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		for (role, op) in self.iter.by_ref() {
+			let node = match op {
+				Visit::Push(node) => node,
+				Visit::Pop(_) => {
+					self.stack.pop();
+					if role.is_some() {
+						self.path.pop();
+					}
+
+					continue;
+				}
+			};
+
+			// Hover text does not inherit the style of the node it belongs to.
+			let mut style = match (role, self.stack.last()) {
+				(Some(PathStep::Extra(_) | PathStep::Arg(_)), Some(parent)) => parent.style.clone(),
+				_ => Style::default(),
+			};
+			style.merge(&node.style);
+
+			if let Some(step) = role {
+				self.path.push(step);
+			}
+
+			self.stack.push(Frame { role, node, style });
+
+			let stack = self.stack.as_slice();
+			if self
+				.query
+				.selectors
+				.iter()
+				.any(|selector| selector.matches(stack))
+			{
+				return Some((self.path.clone(), node));
+			}
+		}
+
+		None
+	}
+}
+
+impl FusedIterator for Matches<'_> {}
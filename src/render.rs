@@ -0,0 +1,457 @@
+//! A module for resolving viewer-dependent content in component trees before they are encoded.
+//!
+//! Servers frequently need to fill in parts of a component differently for each player that sees
+//! it: scoreboard values, keybinds, translations resolved server-side, and so on. The
+//! [ComponentRenderer] trait provides a hook for each [content][Content] type and takes care of
+//! walking the rest of the tree, so implementors only need to override the parts they care about.
+//!
+//! Renderers can be chained together with a [RenderPipeline], which runs each stage in order and
+//! feeds the output of one stage into the next.
+//!
+//! Content that can only be computed at send time can be stored in a tree as a [VirtualComponent]
+//! (see [Component::deferred()]), and is resolved by the [VirtualRenderer] stage.
+//!
+//! # Examples
+//! ```
+//! use typewheel::{Component, Style};
+//! use typewheel::render::ComponentRenderer;
+//!
+//! struct Viewer {
+//!     score: i32,
+//! }
+//!
+//! struct ScoreRenderer;
+//!
+//! impl ComponentRenderer<Viewer> for ScoreRenderer {
+//!     fn render_score(
+//!         &self,
+//!         name: &str,
+//!         objective: &str,
+//!         _value: &str,
+//!         style: &Style,
+//!         viewer: &Viewer,
+//!     ) -> Component {
+//!         let mut out = Component::score(name, objective, viewer.score.to_string());
+//!         out.style = self.render_style(style, viewer);
+//!         out
+//!     }
+//! }
+//!
+//! let component = Component::text("Score: ").with_extra([Component::score("@s", "kills", "")]);
+//! let rendered = ScoreRenderer.render(&component, &Viewer { score: 12 });
+//!
+//! assert_eq!(rendered.extra[0], Component::score("@s", "kills", "12"));
+//! ```
+
+use crate::{Component, Content, HoverEvent, Style};
+use std::any::Any;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// A trait for transforming a component tree based on some context, such as the player that is
+/// going to see it. Renderers always produce a new [Component] and leave their input untouched.
+///
+/// Every method has a default implementation that copies its input, so implementors only need to
+/// override the hooks for the content they want to change. By default, [Self::render()] recurses
+/// into a component's children, the arguments of [translation][Content::Translation] components,
+/// and the text of [HoverEvent::ShowText] hover events.
+///
+/// The content hooks return a component *without* the original node's children; those are
+/// rendered separately and appended to whatever the hook returns.
+pub trait ComponentRenderer<Ctx: ?Sized> {
+	/// Renders a component and all of its children. Overriding this method replaces the default
+	/// recursion entirely.
+	fn render(&self, component: &Component, ctx: &Ctx) -> Component {
+		render_tree(self, component, ctx)
+	}
+
+	/// Renders a node's style. The default implementation renders the contents of
+	/// [HoverEvent::ShowText] hover events and copies everything else.
+	fn render_style(&self, style: &Style, ctx: &Ctx) -> Style {
+		let mut style = style.clone();
+		if let Some(HoverEvent::ShowText(text)) = &mut style.hover_event {
+			**text = self.render(text, ctx);
+		}
+
+		style
+	}
+
+	/// Renders a [text][Content::Text] node.
+	fn render_text(&self, text: &str, style: &Style, ctx: &Ctx) -> Component {
+		Component::create_flat(
+			Content::Text(text.to_owned()),
+			self.render_style(style, ctx),
+		)
+	}
+
+	/// Renders a [translation][Content::Translation] node. The default implementation renders
+	/// each of the translation's arguments.
+	fn render_translation(
+		&self,
+		key: &str,
+		with: &[Component],
+		style: &Style,
+		ctx: &Ctx,
+	) -> Component {
+		let content = Content::Translation {
+			key: key.to_owned(),
+			with: with.iter().map(|arg| self.render(arg, ctx)).collect(),
+		};
+
+		Component::create_flat(content, self.render_style(style, ctx))
+	}
+
+	/// Renders a [keybind][Content::Keybind] node.
+	fn render_keybind(&self, key: &str, style: &Style, ctx: &Ctx) -> Component {
+		Component::create_flat(
+			Content::Keybind(key.to_owned()),
+			self.render_style(style, ctx),
+		)
+	}
+
+	/// Renders a [score][Content::Score] node.
+	fn render_score(
+		&self,
+		name: &str,
+		objective: &str,
+		value: &str,
+		style: &Style,
+		ctx: &Ctx,
+	) -> Component {
+		let content = Content::Score {
+			name: name.to_owned(),
+			objective: objective.to_owned(),
+			value: value.to_owned(),
+		};
+
+		Component::create_flat(content, self.render_style(style, ctx))
+	}
+
+	/// Renders an [empty][Content::Empty] node.
+	fn render_empty(&self, style: &Style, ctx: &Ctx) -> Component {
+		Component::create_flat(Content::Empty, self.render_style(style, ctx))
+	}
+
+	/// Renders a [virtual][Content::Virtual] node. The default implementation keeps the virtual
+	/// content as is, so that a later [VirtualRenderer] stage can still resolve it.
+	fn render_virtual(&self, content: &VirtualContent, style: &Style, ctx: &Ctx) -> Component {
+		Component::create_flat(
+			Content::Virtual(content.clone()),
+			self.render_style(style, ctx),
+		)
+	}
+}
+
+/// The default tree walk used by [ComponentRenderer::render()]. This is exposed so that renderers
+/// overriding [ComponentRenderer::render()] can still fall back to the default behavior.
+pub fn render_tree<Ctx, R>(renderer: &R, component: &Component, ctx: &Ctx) -> Component
+where
+	Ctx: ?Sized,
+	R: ComponentRenderer<Ctx> + ?Sized,
+{
+	let style = &component.style;
+	let mut out = match &component.content {
+		Content::Text(text) => renderer.render_text(text, style, ctx),
+		Content::Translation { key, with } => renderer.render_translation(key, with, style, ctx),
+		Content::Keybind(key) => renderer.render_keybind(key, style, ctx),
+		Content::Score {
+			name,
+			objective,
+			value,
+		} => renderer.render_score(name, objective, value, style, ctx),
+		Content::Empty => renderer.render_empty(style, ctx),
+		Content::Virtual(content) => renderer.render_virtual(content, style, ctx),
+	};
+
+	out.extra.reserve(component.extra.len());
+	out.extra.extend(
+		component
+			.extra
+			.iter()
+			.map(|child| renderer.render(child, ctx)),
+	);
+
+	out
+}
+
+/// A trait for content that can only be computed at render time, such as a player's ping or a
+/// relative timestamp. Virtual components are stored in a tree as [Content::Virtual] nodes and
+/// are turned into concrete components by a [VirtualRenderer].
+///
+/// This trait is implemented for any closure that accepts a context reference and returns a
+/// component.
+pub trait VirtualComponent<Ctx>: Send + Sync + 'static {
+	/// Computes the concrete component for the provided context. The returned component inherits
+	/// the style of the virtual node it replaces.
+	fn render(&self, ctx: &Ctx) -> Component;
+}
+
+impl<Ctx, F> VirtualComponent<Ctx> for F
+where
+	F: Fn(&Ctx) -> Component + Send + Sync + 'static,
+{
+	#[inline(always)]
+	fn render(&self, ctx: &Ctx) -> Component {
+		self(ctx)
+	}
+}
+
+/// The contents of a [Content::Virtual] node. This holds a shared [VirtualComponent] and an
+/// optional fallback component.
+///
+/// Since the renderer cannot be serialized, virtual nodes are written out as their fallback (or
+/// as nothing at all) when a component is serialized without being rendered first. Virtual
+/// content is never produced by deserialization.
+///
+/// Two virtual contents are only equal if they share the same renderer instance.
+#[derive(Clone)]
+pub struct VirtualContent {
+	renderer: Arc<dyn ErasedVirtual>,
+	fallback: Option<Box<Component>>,
+}
+
+impl VirtualContent {
+	/// Creates new virtual content rendered for contexts of type `Ctx`.
+	pub fn new<Ctx: 'static>(renderer: impl VirtualComponent<Ctx>) -> Self {
+		Self {
+			renderer: Arc::new(Typed {
+				inner: renderer,
+				_ctx: PhantomData,
+			}),
+			fallback: None,
+		}
+	}
+
+	/// A builder method for setting the component used in place of this content when it cannot
+	/// be rendered. This method assumes ownership of `self`, and passes it back when it returns.
+	#[must_use]
+	pub fn with_fallback(mut self, fallback: impl Into<Component>) -> Self {
+		self.fallback = Some(Box::new(fallback.into()));
+		self
+	}
+
+	/// Gets the fallback component, if one is set.
+	#[inline]
+	pub fn fallback(&self) -> Option<&Component> {
+		self.fallback.as_deref()
+	}
+
+	/// Takes the fallback component out of this content.
+	pub(crate) fn into_fallback(self) -> Option<Component> {
+		self.fallback.map(|fallback| *fallback)
+	}
+
+	/// Renders this content for the provided context. Returns [None] if this content was created
+	/// for a different context type.
+	pub fn render<Ctx: 'static>(&self, ctx: &Ctx) -> Option<Component> {
+		self.renderer.render(ctx)
+	}
+}
+
+impl PartialEq for VirtualContent {
+	fn eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.renderer, &other.renderer) && self.fallback == other.fallback
+	}
+}
+
+impl Eq for VirtualContent {}
+
+impl Hash for VirtualContent {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		// Renderers are compared by identity, so they are hashed by address.
+		Arc::as_ptr(&self.renderer).cast::<()>().hash(state);
+		self.fallback.hash(state);
+	}
+}
+
+impl std::fmt::Debug for VirtualContent {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("VirtualContent")
+			.field("context", &self.renderer.context_name())
+			.field("fallback", &self.fallback)
+			.finish()
+	}
+}
+
+/// A type-erased [VirtualComponent]. This lets [VirtualContent] live inside [Content] without
+/// making it generic over the context type.
+trait ErasedVirtual: Send + Sync {
+	fn render(&self, ctx: &dyn Any) -> Option<Component>;
+
+	fn context_name(&self) -> &'static str;
+}
+
+struct Typed<Ctx, V> {
+	inner: V,
+	_ctx: PhantomData<fn(&Ctx)>,
+}
+
+impl<Ctx: 'static, V: VirtualComponent<Ctx>> ErasedVirtual for Typed<Ctx, V> {
+	fn render(&self, ctx: &dyn Any) -> Option<Component> {
+		ctx.downcast_ref::<Ctx>().map(|ctx| self.inner.render(ctx))
+	}
+
+	fn context_name(&self) -> &'static str {
+		std::any::type_name::<Ctx>()
+	}
+}
+
+/// A [ComponentRenderer] that resolves [virtual content][Content::Virtual] for its context. Nodes
+/// created for a different context type are replaced with their fallback.
+///
+/// Other renderers leave virtual content unresolved, so this stage can go anywhere in a
+/// [RenderPipeline]. Stages before it do not see the resolved content; stages after it do.
+///
+/// # Examples
+/// ```
+/// use typewheel::Component;
+/// use typewheel::render::{ComponentRenderer, VirtualRenderer};
+///
+/// struct Viewer {
+///     ping: u32,
+/// }
+///
+/// let component = Component::text("Ping: ")
+///     .with_extra([Component::deferred(|viewer: &Viewer| {
+///         Component::text(format!("{}ms", viewer.ping))
+///     })]);
+///
+/// let rendered = VirtualRenderer.render(&component, &Viewer { ping: 42 });
+/// assert_eq!(rendered, Component::text("Ping: ").with_extra(["42ms"]));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VirtualRenderer;
+
+impl<Ctx: 'static> ComponentRenderer<Ctx> for VirtualRenderer {
+	fn render_virtual(&self, content: &VirtualContent, style: &Style, ctx: &Ctx) -> Component {
+		let Some(rendered) = content.render(ctx) else {
+			return virtual_fallback(self, content, style, ctx);
+		};
+
+		// The rendered component may contain virtual content itself.
+		let mut rendered = self.render(&rendered, ctx);
+		let mut effective = self.render_style(style, ctx);
+		effective.merge(&rendered.style);
+		rendered.style = effective;
+
+		rendered
+	}
+}
+
+/// Renders the fallback of a virtual node, or an empty node if it has none.
+fn virtual_fallback<Ctx, R>(
+	renderer: &R,
+	content: &VirtualContent,
+	style: &Style,
+	ctx: &Ctx,
+) -> Component
+where
+	Ctx: ?Sized,
+	R: ComponentRenderer<Ctx> + ?Sized,
+{
+	let extra = content
+		.fallback()
+		.map(|fallback| renderer.render(fallback, ctx))
+		.into_iter()
+		.collect();
+
+	Component::create(Content::Empty, renderer.render_style(style, ctx), extra)
+}
+
+/// An ordered list of [ComponentRenderer]s that are applied one after another. The output of each
+/// stage is used as the input of the next one.
+///
+/// A pipeline is a renderer itself, so pipelines can be nested inside of other pipelines.
+///
+/// # Examples
+/// ```
+/// use typewheel::{Component, Style};
+/// use typewheel::render::{ComponentRenderer, RenderPipeline};
+///
+/// struct Keybinds;
+///
+/// impl ComponentRenderer<()> for Keybinds {
+///     fn render_keybind(&self, key: &str, style: &Style, ctx: &()) -> Component {
+///         Component::text(format!("<{key}>"))
+///     }
+/// }
+///
+/// struct Shout;
+///
+/// impl ComponentRenderer<()> for Shout {
+///     fn render_text(&self, text: &str, style: &Style, ctx: &()) -> Component {
+///         Component::text(text.to_uppercase())
+///     }
+/// }
+///
+/// let pipeline = RenderPipeline::new().then(Keybinds).then(Shout);
+/// let rendered = pipeline.render(&Component::keybind("key.jump"), &());
+///
+/// assert_eq!(rendered, Component::text("<KEY.JUMP>"));
+/// ```
+pub struct RenderPipeline<'a, Ctx: ?Sized> {
+	stages: Vec<Box<dyn ComponentRenderer<Ctx> + Send + Sync + 'a>>,
+}
+
+impl<'a, Ctx: ?Sized> RenderPipeline<'a, Ctx> {
+	/// Creates an empty pipeline. An empty pipeline renders components as exact copies.
+	#[inline]
+	pub fn new() -> Self {
+		Self { stages: Vec::new() }
+	}
+
+	/// Appends a stage to the end of this pipeline.
+	pub fn push(&mut self, renderer: impl ComponentRenderer<Ctx> + Send + Sync + 'a) -> &mut Self {
+		self.stages.push(Box::new(renderer));
+		self
+	}
+
+	/// A builder method for appending a stage to the end of this pipeline. This method assumes
+	/// ownership of `self`, and passes it back when it returns.
+	#[must_use]
+	pub fn then(mut self, renderer: impl ComponentRenderer<Ctx> + Send + Sync + 'a) -> Self {
+		self.push(renderer);
+		self
+	}
+
+	/// Gets the number of stages in this pipeline.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.stages.len()
+	}
+
+	/// Checks if this pipeline has no stages.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.stages.is_empty()
+	}
+}
+
+impl<Ctx: ?Sized> ComponentRenderer<Ctx> for RenderPipeline<'_, Ctx> {
+	fn render(&self, component: &Component, ctx: &Ctx) -> Component {
+		let Some((first, rest)) = self.stages.split_first() else {
+			return component.clone();
+		};
+
+		rest.iter()
+			.fold(first.render(component, ctx), |out, stage| {
+				stage.render(&out, ctx)
+			})
+	}
+}
+
+impl<Ctx: ?Sized> Default for RenderPipeline<'_, Ctx> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<Ctx: ?Sized> std::fmt::Debug for RenderPipeline<'_, Ctx> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("RenderPipeline")
+			.field("stages", &self.stages.len())
+			.finish()
+	}
+}
@@ -46,6 +46,20 @@ impl From<Component> for SerialVessel {
 			}
 		}
 
+		// Virtual content can't be encoded, so it's swapped out for its fallback.
+		if let Content::Virtual(content) = value.content {
+			let mut extra = value.extra;
+			if let Some(fallback) = content.into_fallback() {
+				extra.insert(0, fallback);
+			}
+
+			return Self::Rich {
+				style: value.style,
+				content: Content::Empty,
+				extra,
+			};
+		}
+
 		Self::Rich {
 			style: value.style,
 			content: value.content,
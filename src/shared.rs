@@ -0,0 +1,595 @@
+//! A module for [SharedComponent], a persistent form of [Component] with structural sharing.
+//!
+//! Every node of a shared component is reference-counted and immutable, so cloning one only
+//! increments a reference count, and the same subtree can be part of any number of trees. Editing
+//! a shared component copies only the nodes on the path to the edited node; all other subtrees
+//! stay shared with the original.
+//!
+//! Translation arguments and hover text are stored as shared components too, so copying a node
+//! never copies the trees nested in it.
+//!
+//! # Examples
+//! ```
+//! use typewheel::{Component, TextColor};
+//! use typewheel::shared::SharedComponent;
+//!
+//! let prefix = SharedComponent::from(Component::text("[Server] ").with_color(TextColor::Gold));
+//!
+//! let message = SharedComponent::default().with_extra([
+//!     prefix.clone(),
+//!     Component::text("Restarting soon").into(),
+//! ]);
+//!
+//! // The prefix is stored once, no matter how many messages contain it.
+//! assert!(SharedComponent::ptr_eq(&message.extra()[0], &prefix));
+//!
+//! assert_eq!(
+//!     Component::from(message),
+//!     Component::empty().with_extra([
+//!         Component::text("[Server] ").with_color(TextColor::Gold),
+//!         Component::text("Restarting soon"),
+//!     ])
+//! );
+//! ```
+
+use crate::iter::{IterOrder, Pending, Visit};
+use crate::{Component, Content, HoverEvent, Style};
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+/// A reference-counted, immutable component tree. See the [module docs][self] for more
+/// information.
+///
+/// Shared components can be converted to and from [Component]s with [From]. Converting a
+/// component is a deep copy, while converting back only copies nodes that are still shared.
+#[derive(Clone, Default)]
+pub struct SharedComponent(Arc<Node>);
+
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+struct Node {
+	/// The node's content. The arguments of translations are moved to [Self::args].
+	content: Content,
+
+	/// The node's style. The text of hover events is moved to [Self::hover_text], and an empty
+	/// component is left in its place.
+	style: Style,
+
+	args: Vec<SharedComponent>,
+	hover_text: Option<SharedComponent>,
+	extra: Vec<SharedComponent>,
+}
+
+impl SharedComponent {
+	/// Creates a new shared component with the provided content and no style or children.
+	pub fn new(content: Content) -> Self {
+		Self::from(Component::new(content))
+	}
+
+	/// Builds a shared node from a component, turning the components nested in it into shared
+	/// components with `share`. Every nested component is shared before the node itself is built.
+	/// Returns [None] if `share` does.
+	pub(crate) fn try_build(
+		component: Component,
+		share: &mut impl FnMut(Component) -> Option<SharedComponent>,
+	) -> Option<Self> {
+		let Component {
+			mut content,
+			mut style,
+			extra,
+		} = component;
+
+		let hover_text = match hover_text_mut(&mut style) {
+			Some(text) => Some(share(mem::take(text))?),
+			None => None,
+		};
+
+		let args = match &mut content {
+			Content::Translation { with, .. } => mem::take(with)
+				.into_iter()
+				.map(&mut *share)
+				.collect::<Option<_>>()?,
+			_ => Vec::new(),
+		};
+
+		let extra = extra.into_iter().map(share).collect::<Option<_>>()?;
+
+		Some(Self(Arc::new(Node {
+			content,
+			style,
+			args,
+			hover_text,
+			extra,
+		})))
+	}
+
+	/// Gets this node's content. Translation arguments are stored separately, so translations
+	/// returned by this method have no arguments; use [Self::args()] to get them.
+	#[inline]
+	pub fn content(&self) -> &Content {
+		&self.0.content
+	}
+
+	/// Gets this node's style. Hover text and hovered entity names are stored separately, so hover
+	/// events returned by this method hold an empty component in their place; use
+	/// [Self::hover_text()] to get them.
+	#[inline]
+	pub fn style(&self) -> &Style {
+		&self.0.style
+	}
+
+	/// Gets the arguments of this node, if it is a [translation][Content::Translation].
+	#[inline]
+	pub fn args(&self) -> &[SharedComponent] {
+		&self.0.args
+	}
+
+	/// Gets the text of this node's [ShowText][HoverEvent::ShowText] hover event, or the name of its
+	/// [ShowEntity][HoverEvent::ShowEntity] hover event.
+	#[inline]
+	pub fn hover_text(&self) -> Option<&SharedComponent> {
+		self.0.hover_text.as_ref()
+	}
+
+	/// Gets this node's children.
+	#[inline]
+	pub fn extra(&self) -> &[SharedComponent] {
+		&self.0.extra
+	}
+
+	/// Gets a mutable reference to this node's content. If the node is shared, it is copied first;
+	/// its arguments and children are not.
+	///
+	/// Like [Self::content()], the content has no translation arguments. Arguments written to it
+	/// replace the node's arguments once the returned reference is dropped, and changing it to
+	/// content other than a translation removes them.
+	#[inline]
+	pub fn content_mut(&mut self) -> ContentMut<'_> {
+		ContentMut(Arc::make_mut(&mut self.0))
+	}
+
+	/// Gets a mutable reference to this node's style. If the node is shared, it is copied first;
+	/// its hover text and children are not.
+	///
+	/// Like [Self::style()], hover events hold an empty component in place of their text. Text
+	/// that is written to a hover event replaces the node's hover text once the returned reference
+	/// is dropped, and changing the hover event to one without text removes it.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::{Component, HoverEvent};
+	/// use typewheel::shared::SharedComponent;
+	///
+	/// let original = SharedComponent::from(Component::text("a").with_hover_event(HoverEvent::show_text("b")));
+	///
+	/// let mut edited = original.clone();
+	/// edited.style_mut().bold = Some(true);
+	/// assert!(SharedComponent::ptr_eq(
+	///     original.hover_text().unwrap(),
+	///     edited.hover_text().unwrap()
+	/// ));
+	///
+	/// edited.style_mut().hover_event = Some(HoverEvent::show_text("c"));
+	/// assert_eq!(edited.hover_text().unwrap().to_component(), Component::text("c"));
+	/// ```
+	#[inline]
+	pub fn style_mut(&mut self) -> StyleMut<'_> {
+		StyleMut(Arc::make_mut(&mut self.0))
+	}
+
+	/// Gets a mutable reference to this node's children. If the node is shared, it is copied first;
+	/// the children themselves are not.
+	#[inline]
+	pub fn extra_mut(&mut self) -> &mut Vec<SharedComponent> {
+		&mut Arc::make_mut(&mut self.0).extra
+	}
+
+	/// Appends children to this node. See [Component::append()].
+	pub fn append(&mut self, extra: impl IntoIterator<Item = impl Into<SharedComponent>>) {
+		self.extra_mut().extend(extra.into_iter().map(Into::into));
+	}
+
+	/// A builder method for appending children to this node. See [Component::with_extra()].
+	#[must_use]
+	#[inline]
+	pub fn with_extra(
+		mut self,
+		extra: impl IntoIterator<Item = impl Into<SharedComponent>>,
+	) -> Self {
+		self.append(extra);
+		self
+	}
+
+	/// A builder method for replacing this node's style, including its hover text.
+	#[must_use]
+	#[inline]
+	pub fn with_style(mut self, style: Style) -> Self {
+		let node = Arc::make_mut(&mut self.0);
+		node.style = style;
+		node.hover_text = None;
+		node.split_style();
+		self
+	}
+
+	/// Gets the node at a path, where each element of the path is an index into the children of the
+	/// previous node. An empty path refers to this node itself.
+	pub fn get(&self, path: &[usize]) -> Option<&SharedComponent> {
+		path.iter()
+			.try_fold(self, |node, &index| node.0.extra.get(index))
+	}
+
+	/// Gets a mutable reference to the node at a path. Every node on the path that is shared is
+	/// copied, so that editing the returned node does not affect other trees. Nodes that are not on
+	/// the path stay shared.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::{Component, TextColor};
+	/// use typewheel::shared::SharedComponent;
+	///
+	/// let original = SharedComponent::from(Component::text("a").with_extra(["b", "c"]));
+	///
+	/// let mut edited = original.clone();
+	/// edited.get_mut(&[1]).unwrap().style_mut().color = Some(TextColor::Red);
+	///
+	/// assert_eq!(original.get(&[1]).unwrap().style().color, None);
+	/// assert_eq!(edited.get(&[1]).unwrap().style().color, Some(TextColor::Red));
+	///
+	/// // The unchanged child is still shared.
+	/// assert!(SharedComponent::ptr_eq(&original.extra()[0], &edited.extra()[0]));
+	/// ```
+	pub fn get_mut(&mut self, path: &[usize]) -> Option<&mut SharedComponent> {
+		// Check the path first, so that nothing is copied if it is invalid.
+		self.get(path)?;
+
+		let mut node = self;
+		for &index in path {
+			node = &mut node.extra_mut()[index];
+		}
+
+		Some(node)
+	}
+
+	/// Gets the number of nodes in this tree. See [Component::tree_size()].
+	pub fn tree_size(&self) -> usize {
+		let mut stack = vec![self];
+		let mut count = 0;
+
+		while let Some(node) = stack.pop() {
+			count += 1;
+			stack.extend(node.extra());
+		}
+
+		count
+	}
+
+	/// Checks if two shared components are the same node, as opposed to equal ones.
+	#[inline]
+	pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+		Arc::ptr_eq(&this.0, &other.0)
+	}
+
+	/// Gets the address of this node, for hashing by identity.
+	#[inline]
+	pub(crate) fn as_ptr(&self) -> *const () {
+		Arc::as_ptr(&self.0).cast()
+	}
+
+	/// Gets the number of handles to this node, including the nodes that it is a child of.
+	#[inline]
+	pub(crate) fn strong_count(this: &Self) -> usize {
+		Arc::strong_count(&this.0)
+	}
+
+	/// Converts this shared component into a [Component]. This is a deep copy.
+	pub fn to_component(&self) -> Component {
+		let node = &*self.0;
+		let (content, style) = join(
+			node.content.clone(),
+			node.style.clone(),
+			node.args.iter().map(Self::to_component),
+			node.hover_text.as_ref().map(Self::to_component),
+		);
+
+		Component::create(
+			content,
+			style,
+			node.extra.iter().map(Self::to_component).collect(),
+		)
+	}
+
+	/// Creates an iterator over this tree's nodes. See [Component::iter()].
+	#[inline]
+	pub fn iter(&self) -> SharedIterator<'_> {
+		SharedIterator {
+			queue: VecDeque::from([Pending::Expand(self)]),
+			order: IterOrder::default(),
+		}
+	}
+
+	/// Creates an iterator that traverses this tree while preserving depth context. See
+	/// [Component::visit()].
+	#[inline]
+	pub fn visit(&self) -> SharedVisitingIterator<'_> {
+		SharedVisitingIterator {
+			queue: VecDeque::from([Visit::Push(self)]),
+		}
+	}
+}
+
+impl Node {
+	/// Moves translation arguments that were written to the content into [Self::args]. Content
+	/// without arguments keeps the current ones, unless it is not a translation.
+	fn split_content(&mut self) {
+		match &mut self.content {
+			Content::Translation { with, .. } if !with.is_empty() => {
+				self.args = mem::take(with).into_iter().map(Into::into).collect();
+			}
+			Content::Translation { .. } => {}
+			_ => self.args.clear(),
+		}
+	}
+
+	/// Moves hover text that was written to the style into [Self::hover_text]. Empty hover text
+	/// keeps the current text, unless the hover event does not hold any.
+	fn split_style(&mut self) {
+		match hover_text_mut(&mut self.style) {
+			Some(text) if self.hover_text.is_none() || *text != Component::default() => {
+				self.hover_text = Some(mem::take(text).into());
+			}
+			Some(_) => {}
+			None => self.hover_text = None,
+		}
+	}
+}
+
+/// Gets the text of a style's hover event, if it has any.
+fn hover_text_mut(style: &mut Style) -> Option<&mut Component> {
+	match &mut style.hover_event {
+		Some(HoverEvent::ShowText(text)) => Some(text),
+		Some(HoverEvent::ShowEntity(entity)) => Some(&mut entity.name),
+		_ => None,
+	}
+}
+
+/// Puts the nested components of a node back into its content and style.
+fn join(
+	mut content: Content,
+	mut style: Style,
+	args: impl Iterator<Item = Component>,
+	hover_text: Option<Component>,
+) -> (Content, Style) {
+	if let Content::Translation { with, .. } = &mut content {
+		*with = args.collect();
+	}
+
+	if let (Some(text), Some(hover_text)) = (hover_text_mut(&mut style), hover_text) {
+		*text = hover_text;
+	}
+
+	(content, style)
+}
+
+/// A mutable reference to the content of a [SharedComponent], created by
+/// [SharedComponent::content_mut()]. Translation arguments written to the content are moved out of
+/// it when this is dropped.
+pub struct ContentMut<'a>(&'a mut Node);
+
+impl Deref for ContentMut<'_> {
+	type Target = Content;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		&self.0.content
+	}
+}
+
+impl DerefMut for ContentMut<'_> {
+	#[inline]
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.0.content
+	}
+}
+
+impl Drop for ContentMut<'_> {
+	fn drop(&mut self) {
+		self.0.split_content();
+	}
+}
+
+impl std::fmt::Debug for ContentMut<'_> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.0.content.fmt(f)
+	}
+}
+
+/// A mutable reference to the style of a [SharedComponent], created by
+/// [SharedComponent::style_mut()]. Hover text written to the style is moved out of it when this is
+/// dropped.
+pub struct StyleMut<'a>(&'a mut Node);
+
+impl Deref for StyleMut<'_> {
+	type Target = Style;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		&self.0.style
+	}
+}
+
+impl DerefMut for StyleMut<'_> {
+	#[inline]
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.0.style
+	}
+}
+
+impl Drop for StyleMut<'_> {
+	fn drop(&mut self) {
+		self.0.split_style();
+	}
+}
+
+impl std::fmt::Debug for StyleMut<'_> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.0.style.fmt(f)
+	}
+}
+
+impl PartialEq for SharedComponent {
+	fn eq(&self, other: &Self) -> bool {
+		Self::ptr_eq(self, other) || self.0 == other.0
+	}
+}
+
+impl Eq for SharedComponent {}
+
+impl Hash for SharedComponent {
+	#[inline]
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.0.hash(state);
+	}
+}
+
+impl std::fmt::Debug for SharedComponent {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("SharedComponent")
+			.field("content", &self.0.content)
+			.field("style", &self.0.style)
+			.field("args", &self.0.args)
+			.field("hover_text", &self.0.hover_text)
+			.field("extra", &self.0.extra)
+			.finish()
+	}
+}
+
+impl From<Component> for SharedComponent {
+	fn from(value: Component) -> Self {
+		Self::try_build(value, &mut |nested| Some(Self::from(nested)))
+			.expect("sharing a component always succeeds")
+	}
+}
+
+impl From<SharedComponent> for Component {
+	fn from(value: SharedComponent) -> Self {
+		match Arc::try_unwrap(value.0) {
+			Ok(node) => {
+				let (content, style) = join(
+					node.content,
+					node.style,
+					node.args.into_iter().map(Component::from),
+					node.hover_text.map(Component::from),
+				);
+
+				Component::create(
+					content,
+					style,
+					node.extra.into_iter().map(Component::from).collect(),
+				)
+			}
+			Err(shared) => SharedComponent(shared).to_component(),
+		}
+	}
+}
+
+impl<'a> IntoIterator for &'a SharedComponent {
+	type Item = &'a SharedComponent;
+	type IntoIter = SharedIterator<'a>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+/// A variable-order iterator over [SharedComponent] trees. This is the shared equivalent of a
+/// [FlatIterator][crate::iter::FlatIterator].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SharedIterator<'a> {
+	queue: VecDeque<Pending<&'a SharedComponent, &'a SharedComponent>>,
+	order: IterOrder,
+}
+
+impl SharedIterator<'_> {
+	/// Sets the [iteration order][IterOrder] for this iterator.
+	pub fn with_order(mut self, order: IterOrder) -> Self {
+		self.order = order;
+		self
+	}
+}
+
+impl<'a> Iterator for SharedIterator<'a> {
+	type Item = &'a SharedComponent;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.order
+			.advance(&mut self.queue, |node| node.extra().iter())
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let remaining = self
+			.queue
+			.iter()
+			.map(|pending| match pending {
+				Pending::Expand(node) => node.tree_size(),
+				Pending::Emit(_) => 1,
+			})
+			.sum();
+
+		(remaining, Some(remaining))
+	}
+}
+
+impl ExactSizeIterator for SharedIterator<'_> {}
+
+impl FusedIterator for SharedIterator<'_> {}
+
+/// An iterator over [SharedComponent] trees that emits [Visit] operations. This is the shared
+/// equivalent of a [VisitingIterator][crate::iter::VisitingIterator].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SharedVisitingIterator<'a> {
+	queue: VecDeque<Visit<'a, SharedComponent>>,
+}
+
+impl<'a> Iterator for SharedVisitingIterator<'a> {
+	type Item = Visit<'a, SharedComponent>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let op = self.queue.pop_front();
+		if let Some(Visit::Push(item)) = op {
+			let extra = item.extra();
+
+			self.queue.reserve(extra.len() + 1);
+			self.queue.push_front(Visit::Pop(item));
+
+			for child in extra.iter().rev() {
+				self.queue.push_front(Visit::Push(child));
+			}
+		}
+
+		op
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		// Every node is pushed and popped once.
+		let remaining = self
+			.queue
+			.iter()
+			.map(|op| match op {
+				Visit::Push(node) => 2 * node.tree_size(),
+				Visit::Pop(_) => 1,
+			})
+			.sum();
+
+		(remaining, Some(remaining))
+	}
+}
+
+impl ExactSizeIterator for SharedVisitingIterator<'_> {}
+
+impl FusedIterator for SharedVisitingIterator<'_> {}
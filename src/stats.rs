@@ -0,0 +1,274 @@
+//! A module for measuring component trees. [Component::stats()] walks a tree once and reports
+//! [ComponentStats], which are useful for enforcing limits on untrusted input and for keeping an
+//! eye on how large messages get before they are sent.
+//!
+//! # Examples
+//! ```
+//! use typewheel::{ClickEvent, Component, TextColor};
+//!
+//! let component = Component::text("Click ")
+//!     .with_color(TextColor::Gray)
+//!     .with_extra([Component::text("here").with_click_event(ClickEvent::copy("hi"))]);
+//!
+//! let stats = component.stats();
+//! assert_eq!(stats.nodes, 2);
+//! assert_eq!(stats.text_length, 10);
+//! assert_eq!(stats.style.color, 1);
+//! assert_eq!(stats.events.copy, 1);
+//! ```
+
+use crate::visitor::{self, ComponentVisitor};
+use crate::{ClickEvent, Component, HoverEvent, Style, TextColor};
+
+/// Statistics about a component tree. Nodes nested in translation arguments and hover events are
+/// included, since they are displayed to players too.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ComponentStats {
+	/// The number of nodes in the tree, including translation arguments, hover text and hovered
+	/// entity names.
+	pub nodes: usize,
+
+	/// The [depth][Component::depth()] of the tree.
+	pub depth: usize,
+
+	/// The total length of all [text][crate::Content::Text] content, in characters.
+	pub text_length: usize,
+
+	/// How many nodes set each style field.
+	pub style: StyleUsage,
+
+	/// How many events of each kind the tree contains.
+	pub events: EventCounts,
+
+	/// A rough estimate of the size of the tree's JSON encoding, in bytes. This is computed without
+	/// encoding the tree, and does not account for escaped characters.
+	pub estimated_json_size: usize,
+}
+
+/// The number of nodes that set each [Style] field.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct StyleUsage {
+	/// The number of nodes that set [Style::bold].
+	pub bold: usize,
+
+	/// The number of nodes that set [Style::italic].
+	pub italic: usize,
+
+	/// The number of nodes that set [Style::underlined].
+	pub underlined: usize,
+
+	/// The number of nodes that set [Style::strikethrough].
+	pub strikethrough: usize,
+
+	/// The number of nodes that set [Style::obfuscated].
+	pub obfuscated: usize,
+
+	/// The number of nodes that set [Style::font].
+	pub font: usize,
+
+	/// The number of nodes that set [Style::color].
+	pub color: usize,
+
+	/// The number of nodes that set [Style::insertion].
+	pub insertion: usize,
+
+	/// The number of nodes that set [Style::click_event].
+	pub click_event: usize,
+
+	/// The number of nodes that set [Style::hover_event].
+	pub hover_event: usize,
+}
+
+/// The number of events of each kind in a component tree.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct EventCounts {
+	/// The number of [ClickEvent::RunCommand] events.
+	pub run_command: usize,
+
+	/// The number of [ClickEvent::SuggestCommand] events.
+	pub suggest_command: usize,
+
+	/// The number of [ClickEvent::ChangePage] events.
+	pub change_page: usize,
+
+	/// The number of [ClickEvent::Copy] events.
+	pub copy: usize,
+
+	/// The number of [HoverEvent::ShowText] events.
+	pub show_text: usize,
+
+	/// The number of [HoverEvent::ShowItem] events.
+	pub show_item: usize,
+
+	/// The number of [HoverEvent::ShowEntity] events.
+	pub show_entity: usize,
+}
+
+impl Component {
+	/// Measures this component tree. See [ComponentStats] for more information.
+	pub fn stats(&self) -> ComponentStats {
+		let mut stats = ComponentStats {
+			depth: self.depth(),
+			..Default::default()
+		};
+
+		Collector(&mut stats).visit_component(self);
+		stats
+	}
+}
+
+/// A visitor that adds up the stats for every node it visits.
+struct Collector<'a>(&'a mut ComponentStats);
+
+impl Collector<'_> {
+	/// Adds the estimated size of an object field to the JSON size: the key, its quotes, a colon,
+	/// the value, and a separating comma.
+	fn field(&mut self, key: &str, value: usize) {
+		self.0.estimated_json_size += key.len() + value + 4;
+	}
+}
+
+impl ComponentVisitor for Collector<'_> {
+	fn visit_component(&mut self, component: &Component) {
+		self.0.nodes += 1;
+
+		// Unstyled text leaves are encoded as plain strings.
+		if let (Some(text), true, true) = (
+			component.shallow_text(),
+			component.style.is_blank(),
+			component.extra.is_empty(),
+		) {
+			self.0.text_length += text.chars().count();
+			self.0.estimated_json_size += text.len() + 2;
+			return;
+		}
+
+		// Braces, minus the comma after the last field.
+		self.0.estimated_json_size += 1;
+		visitor::walk_component(self, component);
+
+		if !component.extra.is_empty() {
+			// Brackets, minus the comma after the last child.
+			self.field("extra", component.extra.len() + 1);
+		}
+	}
+
+	fn visit_text(&mut self, text: &str) {
+		self.0.text_length += text.chars().count();
+		self.field("text", text.len() + 2);
+	}
+
+	fn visit_translation(&mut self, key: &str, with: &[Component]) {
+		self.field("translate", key.len() + 2);
+		if !with.is_empty() {
+			self.field("with", with.len() + 1);
+		}
+
+		for arg in with {
+			self.visit_component(arg);
+		}
+	}
+
+	fn visit_keybind(&mut self, key: &str) {
+		self.field("keybind", key.len() + 2);
+	}
+
+	fn visit_score(&mut self, name: &str, objective: &str, value: &str) {
+		// {"name":"","objective":"","value":""}
+		self.field("score", name.len() + objective.len() + value.len() + 37);
+	}
+
+	fn visit_style(&mut self, style: &Style) {
+		let usage = &mut self.0.style;
+		let flags = [
+			(style.bold, &mut usage.bold, "bold"),
+			(style.italic, &mut usage.italic, "italic"),
+			(style.underlined, &mut usage.underlined, "underlined"),
+			(
+				style.strikethrough,
+				&mut usage.strikethrough,
+				"strikethrough",
+			),
+			(style.obfuscated, &mut usage.obfuscated, "obfuscated"),
+		];
+
+		let mut size = 0;
+		for (flag, count, key) in flags {
+			if let Some(flag) = flag {
+				*count += 1;
+				size += key.len() + if flag { 4 } else { 5 } + 4;
+			}
+		}
+
+		self.0.estimated_json_size += size;
+		visitor::walk_style(self, style);
+	}
+
+	fn visit_font(&mut self, font: &str) {
+		self.0.style.font += 1;
+		self.field("font", font.len() + 2);
+	}
+
+	fn visit_color(&mut self, _color: &TextColor) {
+		self.0.style.color += 1;
+
+		// Named colors are about as long as hex colors on average.
+		self.field("color", 9);
+	}
+
+	fn visit_insertion(&mut self, insertion: &str) {
+		self.0.style.insertion += 1;
+		self.field("insertion", insertion.len() + 2);
+	}
+
+	fn visit_click_event(&mut self, event: &ClickEvent) {
+		self.0.style.click_event += 1;
+
+		let events = &mut self.0.events;
+		let value = match event {
+			ClickEvent::RunCommand(command) => {
+				events.run_command += 1;
+				command.len() + 2
+			}
+			ClickEvent::SuggestCommand(command) => {
+				events.suggest_command += 1;
+				command.len() + 2
+			}
+			ClickEvent::ChangePage(page) => {
+				events.change_page += 1;
+				page.to_string().len()
+			}
+			ClickEvent::Copy(text) => {
+				events.copy += 1;
+				text.len() + 2
+			}
+		};
+
+		// {"action":"...","value":...}
+		self.field("click_event", value + 40);
+	}
+
+	fn visit_hover_event(&mut self, event: &HoverEvent) {
+		self.0.style.hover_event += 1;
+
+		// {"action":"...","contents":...}, plus a rough size for items and entities, whose
+		// contents are not components.
+		self.field("hover_event", 40);
+		match event {
+			HoverEvent::ShowText(_) => self.0.events.show_text += 1,
+			HoverEvent::ShowItem(item) => {
+				self.0.events.show_item += 1;
+				self.0.estimated_json_size += item.id.to_string().len() + 24;
+			}
+			HoverEvent::ShowEntity(entity) => {
+				self.0.events.show_entity += 1;
+				self.0.estimated_json_size += entity.entity_type.to_string().len() + 64;
+			}
+		}
+
+		visitor::walk_hover_event(self, event);
+	}
+}
@@ -1,6 +1,5 @@
 use crate::event::{ClickEvent, HoverEvent};
 use serde::{Deserialize, Serialize};
-use std::mem;
 
 /// This is quite a hefty macro, so let's break down what it does:
 ///
@@ -133,6 +132,31 @@ macro_rules! style_fields {
 					}
 				)+
 			}
+
+			/// Clears every field that is set to the same value as in `parent`. If `parent` is the
+			/// effective style that this style inherits from, the result renders the same way.
+			pub(crate) fn strip_inherited(&mut self, parent: &Self) {
+				$(
+					if self.$field.is_some() && self.$field == parent.$field {
+						self.$field = None;
+					}
+				)+
+			}
+
+			/// Checks if every field that is set in `other` is also set in this style, meaning that
+			/// this style overrides all of `other` when merged onto it.
+			pub(crate) fn covers(&self, other: &Self) -> bool {
+				$((self.$field.is_some() || other.$field.is_none()) )&&+
+			}
+
+			/// Clears every field that is not set to the same value in `other`.
+			pub(crate) fn intersect(&mut self, other: &Self) {
+				$(
+					if self.$field != other.$field {
+						self.$field = None;
+					}
+				)+
+			}
 		}
 
 		impl std::default::Default for $name {
@@ -213,7 +237,7 @@ style_fields! {
 	/// on non-exhaustive structs. To respect the semver implications of this, new fields will incur
 	/// a minor version bump.
 	///
-	#[derive(PartialEq, Eq)]
+	#[derive(PartialEq, Eq, Hash)]
 	pub struct Style;
 
 	/// Controls whether this component and its children render in bold. Defaults to `false` if
@@ -595,7 +619,7 @@ impl Style {
 	/// assert_eq!(style.italic, None);
 	/// ```
 	pub fn clear(&mut self) {
-		mem::swap(self, &mut Default::default());
+		*self = Self::BLANK;
 	}
 }
 
@@ -608,7 +632,7 @@ impl Style {
 /// # Serial Representation
 /// Named text colors are serialized in `snake_case`. Each variant documents its own string
 /// representation for clarity. Hex colors are serialized as hex strings with a leading `#`.
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum TextColor {
 	/// Named color `black` (hex `#000`; code `§0`).
@@ -0,0 +1,248 @@
+//! A module for precompiled message templates. A [Template] is compiled once from a component
+//! containing [placeholders][crate::placeholder], and can then be rendered many times with
+//! different arguments without scanning the component's text again.
+//!
+//! # Examples
+//! ```
+//! use typewheel::{Component, TextColor};
+//! use typewheel::template::Template;
+//!
+//! let template = Template::compile(
+//!     Component::text("<player> joined the game").with_color(TextColor::Yellow),
+//! );
+//! assert_eq!(template.slots(), ["player"]);
+//!
+//! let message = template.render(&[Component::text("Notch")]);
+//! assert_eq!(
+//!     message,
+//!     Component::empty()
+//!         .with_color(TextColor::Yellow)
+//!         .with_extra(["Notch", " joined the game"])
+//! );
+//! ```
+
+use crate::path::ComponentPath;
+use crate::placeholder::{Placeholder, PlaceholderReplacer, PlaceholderResolver};
+use crate::Component;
+use std::cell::RefCell;
+
+/// A component with argument slots, compiled from a component containing `<name>` placeholders.
+/// Each distinct placeholder name becomes a slot, numbered in the order in which the names first
+/// appear.
+///
+/// Rendering a template clones its static parts once and writes each argument directly into its
+/// slot. With the `json` crate feature, templates also keep a pre-serialized copy of their static
+/// parts, so [Self::write_json()] only has to encode the arguments.
+#[derive(Clone, Debug)]
+pub struct Template {
+	tree: Component,
+	slots: Vec<String>,
+
+	/// The location of every slot node in [Self::tree], and the slot it holds.
+	positions: Vec<(ComponentPath, usize)>,
+
+	/// Pre-serialized JSON for the static parts of the template. Arguments go between fragments,
+	/// so there is always one more fragment than there are positions.
+	#[cfg(feature = "json")]
+	fragments: Vec<(String, usize)>,
+	#[cfg(feature = "json")]
+	trailer: String,
+}
+
+impl Template {
+	/// Compiles a template from a component. Placeholders are found the same way as with a
+	/// [PlaceholderReplacer], including in translation arguments and hover text.
+	pub fn compile(component: Component) -> Self {
+		let slots = RefCell::new(Vec::<String>::new());
+		let resolver = |name: &str| {
+			let mut slots = slots.borrow_mut();
+			let index = match slots.iter().position(|slot| slot == name) {
+				Some(index) => index,
+				None => {
+					slots.push(name.to_owned());
+					slots.len() - 1
+				}
+			};
+
+			Some(Placeholder::Component(marker(index)))
+		};
+
+		let tree = PlaceholderReplacer::new(resolver).replace(component);
+
+		// Paths are yielded in the order that the nodes are serialized.
+		let positions: Vec<_> = tree
+			.iter_paths()
+			.with_translate_args()
+			.with_hover_text()
+			.filter_map(|(path, node)| Some((path, marker_slot(node)?)))
+			.collect();
+
+		#[cfg(feature = "json")]
+		let (fragments, trailer) = json::split(&tree, &positions);
+
+		Self {
+			tree,
+			slots: slots.into_inner(),
+			positions,
+			#[cfg(feature = "json")]
+			fragments,
+			#[cfg(feature = "json")]
+			trailer,
+		}
+	}
+
+	/// Compiles a template from a string. The string is treated as unstyled text; to compile
+	/// styled markup, decode it with a [codec][crate::codec] first and use [Self::compile()].
+	///
+	/// # Examples
+	/// ```
+	/// # use typewheel::template::Template;
+	/// let template = Template::parse("<a> and <b>, then <a> again");
+	/// assert_eq!(template.slots(), ["a", "b"]);
+	/// ```
+	#[inline]
+	pub fn parse(text: impl Into<String>) -> Self {
+		Self::compile(Component::text(text))
+	}
+
+	/// Gets the names of this template's slots, in slot order.
+	#[inline]
+	pub fn slots(&self) -> &[String] {
+		&self.slots
+	}
+
+	/// Gets the index of the slot with the provided name.
+	pub fn slot(&self, name: &str) -> Option<usize> {
+		self.slots.iter().position(|slot| slot == name)
+	}
+
+	/// Renders this template with positional arguments. Each argument fills the slot with the same
+	/// index; slots without an argument are left empty.
+	pub fn render(&self, args: &[Component]) -> Component {
+		let mut out = self.tree.clone();
+
+		for (path, slot) in &self.positions {
+			let node = out.get_mut(path).expect("template slot paths are valid");
+			*node = args.get(*slot).cloned().unwrap_or_default();
+		}
+
+		out
+	}
+
+	/// Renders this template with arguments looked up by slot name. Values that are
+	/// [styles][Placeholder::Style] cannot fill a slot and are left empty.
+	pub fn render_with(&self, resolver: &impl PlaceholderResolver) -> Component {
+		let args: Vec<_> = self
+			.slots
+			.iter()
+			.map(|name| match resolver.resolve(name) {
+				Some(Placeholder::Component(component)) => component,
+				Some(Placeholder::Text(text)) => Component::text(text),
+				_ => Component::empty(),
+			})
+			.collect();
+
+		self.render(&args)
+	}
+
+	/// Writes this template as JSON with positional arguments. The static parts of the template
+	/// are copied from a pre-serialized buffer, so only the arguments are encoded. The output is
+	/// identical to encoding the result of [Self::render()] with a
+	/// [JsonCodec][crate::codec::JsonCodec].
+	///
+	/// # Examples
+	/// ```
+	/// # use typewheel::{Component, codec::{ComponentCodec, JsonCodec}};
+	/// # use typewheel::template::Template;
+	/// let template = Template::parse("Hello, <name>!");
+	/// let args = [Component::text("Notch").with_bold(true)];
+	///
+	/// let mut out = Vec::new();
+	/// template.write_json(&args, &mut out).unwrap();
+	///
+	/// let json = String::from_utf8(out).unwrap();
+	/// assert_eq!(json, JsonCodec.serialize(&template.render(&args)).unwrap());
+	/// ```
+	#[cfg(feature = "json")]
+	pub fn write_json(
+		&self,
+		args: &[Component],
+		mut writer: impl std::io::Write,
+	) -> Result<(), serde_json::Error> {
+		const EMPTY: Component = Component::empty();
+
+		for (fragment, slot) in &self.fragments {
+			writer
+				.write_all(fragment.as_bytes())
+				.map_err(serde_json::Error::io)?;
+			serde_json::to_writer(&mut writer, args.get(*slot).unwrap_or(&EMPTY))?;
+		}
+
+		writer
+			.write_all(self.trailer.as_bytes())
+			.map_err(serde_json::Error::io)
+	}
+
+	/// Renders this template directly to a JSON string. See [Self::write_json()].
+	#[cfg(feature = "json")]
+	pub fn to_json(&self, args: &[Component]) -> Result<String, serde_json::Error> {
+		let mut out = Vec::with_capacity(self.trailer.len() + self.fragments.len() * 32);
+		self.write_json(args, &mut out)?;
+
+		// serde_json only ever writes valid UTF-8.
+		Ok(String::from_utf8(out).expect("JSON output is valid UTF-8"))
+	}
+}
+
+/// Creates the node that temporarily marks a slot in a compiled tree. Null characters are used
+/// because they cannot appear in a placeholder name and are very unlikely in real text.
+fn marker(slot: usize) -> Component {
+	Component::text(format!("\0{slot}\0"))
+}
+
+/// Reads the slot index out of a marker node.
+fn marker_slot(node: &Component) -> Option<usize> {
+	if !node.extra.is_empty() || !node.style.is_blank() {
+		return None;
+	}
+
+	node.shallow_text()?
+		.strip_prefix('\0')?
+		.strip_suffix('\0')?
+		.parse()
+		.ok()
+}
+
+#[cfg(feature = "json")]
+mod json {
+	use crate::path::ComponentPath;
+	use crate::Component;
+
+	/// Serializes a compiled tree and splits it around its marker nodes. Returns each static
+	/// fragment with the slot that follows it, plus the final fragment.
+	pub(super) fn split(
+		tree: &Component,
+		positions: &[(ComponentPath, usize)],
+	) -> (Vec<(String, usize)>, String) {
+		// Templates only contain serializable values, so this can't fail.
+		let mut rest = serde_json::to_string(tree).expect("templates are serializable");
+		let mut fragments = Vec::with_capacity(positions.len());
+
+		for (_, slot) in positions {
+			let encoded = format!("\"\\u0000{slot}\\u0000\"");
+			// Markers are unstyled leaves, so they are always encoded as plain strings, and they are
+			// found in the same order that they are serialized in. A missing marker would shift every
+			// later fragment onto the wrong slot, so it must not be skipped.
+			let index = rest
+				.find(&encoded)
+				.expect("marker nodes are serialized in path order");
+
+			let tail = rest.split_off(index + encoded.len());
+			rest.truncate(index);
+			fragments.push((rest, *slot));
+			rest = tail;
+		}
+
+		(fragments, rest)
+	}
+}
@@ -0,0 +1,252 @@
+//! Shared machinery for replacing ranges of text in a component tree, where a range can start in
+//! one text node and end in one of its siblings.
+
+use crate::placeholder::Placeholder;
+use crate::{Component, Content, HoverEvent, Style};
+use std::ops::Range;
+
+/// The concatenated text of a component tree, in display order.
+#[derive(Default)]
+pub(crate) struct Flow {
+	pub(crate) text: String,
+
+	/// Each text node in [Self::text], in depth-first order.
+	pub(crate) leaves: Vec<Leaf>,
+}
+
+/// A single text node in a [Flow].
+pub(crate) struct Leaf {
+	/// The byte range of the node's text in [Flow::text].
+	pub(crate) range: Range<usize>,
+
+	/// The effective style of the node, including the style that the flow inherits from the node
+	/// it is nested in.
+	pub(crate) style: Style,
+}
+
+impl Flow {
+	/// Collects the text flow of a component tree. Content other than text is written as a null
+	/// character, which breaks up the text so that matches can't span across it. `base` is the
+	/// effective style that the tree inherits.
+	pub(crate) fn collect(component: &Component, base: &Style) -> Self {
+		let mut flow = Self::default();
+		flow.push(component, base);
+		flow
+	}
+
+	fn push(&mut self, node: &Component, parent: &Style) {
+		let mut style = parent.clone();
+		style.merge(&node.style);
+
+		match &node.content {
+			Content::Text(text) => {
+				let start = self.text.len();
+				self.text.push_str(text);
+				self.leaves.push(Leaf {
+					range: start..self.text.len(),
+					style: style.clone(),
+				});
+			}
+			Content::Empty => {}
+			_ => self.text.push('\0'),
+		}
+
+		for child in &node.extra {
+			self.push(child, &style);
+		}
+	}
+
+	/// Gets the leaf containing a byte offset.
+	pub(crate) fn leaf_at(&self, offset: usize) -> Option<&Leaf> {
+		let index = self.leaves.partition_point(|leaf| leaf.range.end <= offset);
+		self.leaves
+			.get(index)
+			.filter(|leaf| leaf.range.contains(&offset))
+	}
+}
+
+/// Replaces ranges of text in a component tree. `find` is called with the flow of the tree, and
+/// returns the ranges to replace in ascending order, along with their replacement values.
+///
+/// Replacement values are inserted into the node that their range starts in. The translation
+/// arguments and hover text of every node are handled as separate flows.
+///
+/// If `remove_empty` is set, nodes that are left with no content and no children because all of
+/// their text was replaced are removed from their parents. Otherwise, they are kept as empty nodes.
+pub(crate) fn replace_ranges(
+	component: Component,
+	remove_empty: bool,
+	find: &mut impl FnMut(&Flow) -> Vec<(Range<usize>, Placeholder)>,
+) -> Component {
+	replace_flow(component, &Style::BLANK, remove_empty, find)
+}
+
+/// Replaces ranges in a single text flow, whose root inherits the effective style `base`.
+fn replace_flow(
+	mut component: Component,
+	base: &Style,
+	remove_empty: bool,
+	find: &mut impl FnMut(&Flow) -> Vec<(Range<usize>, Placeholder)>,
+) -> Component {
+	let flow = Flow::collect(&component, base);
+	let matches = find(&flow);
+
+	let edits = edits(&flow, &matches);
+	apply(
+		&mut component,
+		base,
+		&mut edits.into_iter(),
+		remove_empty,
+		find,
+	);
+
+	component
+}
+
+/// Computes the replacement pieces for every text node in a flow. Nodes that do not need to
+/// change have an entry of [None].
+fn edits(flow: &Flow, matches: &[(Range<usize>, Placeholder)]) -> Vec<Option<Vec<Piece>>> {
+	let mut pending = matches.iter().peekable();
+	flow.leaves
+		.iter()
+		.map(|Leaf { range: leaf, .. }| {
+			// Skip matches that ended before this leaf.
+			while pending
+				.next_if(|(range, _)| range.end <= leaf.start)
+				.is_some()
+			{}
+
+			let mut pieces = Vec::new();
+			let mut cursor = leaf.start;
+			let mut edited = false;
+
+			for (range, value) in pending.clone() {
+				if range.start >= leaf.end {
+					break;
+				}
+
+				edited = true;
+				if range.start >= leaf.start {
+					push_text(&mut pieces, &flow.text[cursor..range.start]);
+					match value {
+						Placeholder::Text(text) => push_text(&mut pieces, text),
+						value => pieces.push(Piece::Value(value.clone())),
+					}
+				}
+
+				cursor = range.end.min(leaf.end);
+			}
+
+			push_text(&mut pieces, &flow.text[cursor..leaf.end]);
+			edited.then_some(pieces)
+		})
+		.collect()
+}
+
+/// Applies computed edits to a tree. This walks the tree in the same order as
+/// [Flow::collect()], keeping track of the effective style of each node. Returns true if all of
+/// the node's text was removed, leaving it with nothing to display.
+fn apply(
+	node: &mut Component,
+	parent: &Style,
+	edits: &mut impl Iterator<Item = Option<Vec<Piece>>>,
+	remove_empty: bool,
+	find: &mut impl FnMut(&Flow) -> Vec<(Range<usize>, Placeholder)>,
+) -> bool {
+	let pieces = match &node.content {
+		Content::Text(_) => edits.next().flatten(),
+		_ => None,
+	};
+
+	let mut style = parent.clone();
+	style.merge(&node.style);
+
+	node.extra.retain_mut(|child| {
+		// This has to run for every child, since each one consumes its own edits.
+		let emptied = apply(child, &style, edits, remove_empty, find);
+		!(remove_empty && emptied)
+	});
+
+	replace_nested(node, &style, remove_empty, find);
+
+	let Some(pieces) = pieces else {
+		return false;
+	};
+
+	let mut pieces = pieces.into_iter().peekable();
+	node.content = match pieces.next_if(|piece| matches!(piece, Piece::Text(_))) {
+		Some(Piece::Text(text)) => Content::Text(text),
+		_ => Content::Empty,
+	};
+
+	let children = std::mem::take(&mut node.extra);
+	node.extra = assemble(pieces, children);
+
+	node.content == Content::Empty && node.extra.is_empty()
+}
+
+/// Replaces ranges in the separate text flows of a node: translation arguments and hover text.
+/// `style` is the effective style of the node, which its translation arguments inherit.
+fn replace_nested(
+	node: &mut Component,
+	style: &Style,
+	remove_empty: bool,
+	find: &mut impl FnMut(&Flow) -> Vec<(Range<usize>, Placeholder)>,
+) {
+	if let Content::Translation { with, .. } = &mut node.content {
+		for arg in with {
+			*arg = replace_flow(std::mem::take(arg), style, remove_empty, find);
+		}
+	}
+
+	// Hover text does not inherit the style of the node it belongs to.
+	match &mut node.style.hover_event {
+		Some(HoverEvent::ShowText(text)) => {
+			**text = replace_ranges(std::mem::take(text), remove_empty, find);
+		}
+		Some(HoverEvent::ShowEntity(entity)) => {
+			entity.name = replace_ranges(std::mem::take(&mut entity.name), remove_empty, find);
+		}
+		_ => {}
+	}
+}
+
+/// A piece of a text node after ranges have been replaced.
+enum Piece {
+	Text(String),
+	Value(Placeholder),
+}
+
+/// Pushes text onto a list of pieces, merging it with the previous piece if possible.
+fn push_text(pieces: &mut Vec<Piece>, text: &str) {
+	if text.is_empty() {
+		return;
+	}
+
+	match pieces.last_mut() {
+		Some(Piece::Text(last)) => last.push_str(text),
+		_ => pieces.push(Piece::Text(text.to_owned())),
+	}
+}
+
+/// Turns a list of pieces into components, followed by a node's original children. Style values
+/// wrap everything that comes after them.
+fn assemble(mut pieces: impl Iterator<Item = Piece>, children: Vec<Component>) -> Vec<Component> {
+	let mut out = Vec::new();
+
+	while let Some(piece) = pieces.next() {
+		match piece {
+			Piece::Text(text) => out.push(Component::text(text)),
+			Piece::Value(Placeholder::Component(component)) => out.push(component),
+			Piece::Value(Placeholder::Text(text)) => out.push(Component::text(text)),
+			Piece::Value(Placeholder::Style(style)) => {
+				let rest = assemble(pieces, children);
+				out.push(Component::create(Content::Empty, style, rest));
+				return out;
+			}
+		}
+	}
+
+	out.extend(children);
+	out
+}
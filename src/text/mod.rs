@@ -0,0 +1,77 @@
+//! A module for working with the text of a component as it is displayed, independently of the
+//! shape of the component tree.
+//!
+//! Text offsets used by this module count through the same text that the [plain text
+//! codec][crate::codec::PlainTextCodec] outputs: the [text][crate::Content::Text] content of each
+//! node, in depth-first order. Offsets can be counted in several [units][TextUnit].
+//!
+//! Patterns can be found and replaced in that text with a [TextReplacer], even where they span
+//! several nodes. A component can also be flattened into a list of [StyledRun]s, which describe the
+//! same text without the structure of the tree.
+
+pub(crate) mod flow;
+mod replace;
+mod runs;
+mod slice;
+
+pub use self::{
+	replace::{TextMatch, TextReplacer},
+	runs::StyledRun,
+};
+
+/// The unit that text offsets are counted in.
+///
+/// # Examples
+/// ```
+/// use typewheel::Component;
+/// use typewheel::text::TextUnit;
+///
+/// let component = Component::text("a😀b");
+///
+/// assert_eq!(component.slice_by(1..2, TextUnit::Chars), Component::text("😀"));
+/// assert_eq!(component.slice_by(1..3, TextUnit::Utf16), Component::text("😀"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TextUnit {
+	/// Counts Unicode scalar values ([char]s). This is the default.
+	#[default]
+	Chars,
+
+	/// Counts UTF-16 code units, matching the indices used by Java strings. Offsets that point into
+	/// the middle of a surrogate pair are rounded down to the start of the pair.
+	Utf16,
+
+	/// Counts extended grapheme clusters, or in other words, characters as a user perceives them.
+	/// Requires the `graphemes` crate feature.
+	#[cfg(feature = "graphemes")]
+	Graphemes,
+}
+
+impl TextUnit {
+	/// Converts an offset in this unit to a byte offset into `text`. Offsets past the end of the
+	/// text are clamped to its length.
+	pub(crate) fn byte_offset(self, text: &str, offset: usize) -> usize {
+		let found = match self {
+			Self::Chars => text.char_indices().nth(offset).map(|(index, _)| index),
+			Self::Utf16 => {
+				let mut units = 0;
+				text.char_indices()
+					.find(|(_, c)| {
+						units += c.len_utf16();
+						units > offset
+					})
+					.map(|(index, _)| index)
+			}
+			#[cfg(feature = "graphemes")]
+			Self::Graphemes => {
+				use unicode_segmentation::UnicodeSegmentation;
+				text.grapheme_indices(true)
+					.nth(offset)
+					.map(|(index, _)| index)
+			}
+		};
+
+		found.unwrap_or(text.len())
+	}
+}
@@ -0,0 +1,310 @@
+use super::flow::{replace_ranges, Flow};
+use crate::placeholder::Placeholder;
+use crate::render::ComponentRenderer;
+use crate::{Component, Style};
+use std::cell::Cell;
+use std::ops::Range;
+
+/// A pattern searched for by a [TextReplacer].
+#[derive(Clone, Debug)]
+enum Pattern {
+	Literal(String),
+	#[cfg(feature = "regex")]
+	Regex(regex::Regex),
+}
+
+/// Replaces matches of a pattern in the text of component trees.
+///
+/// The pattern is searched for in the concatenated text of a tree as it would be displayed, so a
+/// match can start in one text node and end in one of its siblings. Each match is replaced with a
+/// component computed from a [TextMatch], which is inserted into the node that the match starts
+/// in. Text in the other nodes that the match covers is removed, and everything outside of the
+/// matches keeps its original styling.
+///
+/// Like [placeholders][crate::placeholder], matches are also replaced in translation arguments and
+/// hover text, which are searched separately. Content other than text breaks up the text, so
+/// matches can't span across it. Empty matches are ignored.
+///
+/// This type also implements [ComponentRenderer], so it can be used as a stage in a [render
+/// pipeline][crate::render::RenderPipeline].
+///
+/// # Examples
+/// ```
+/// use typewheel::{Component, TextColor};
+/// use typewheel::text::TextReplacer;
+///
+/// let replacer = TextReplacer::literal("hello", |found| {
+///     Component::text(found.text().to_uppercase()).with_color(TextColor::Gold)
+/// });
+///
+/// let component = Component::text("he").with_extra([Component::text("llo!").with_bold(true)]);
+/// assert_eq!(
+///     replacer.replace(component),
+///     Component::empty().with_extra([
+///         Component::text("HELLO").with_color(TextColor::Gold),
+///         Component::text("!").with_bold(true),
+///     ])
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct TextReplacer<F> {
+	pattern: Pattern,
+	replacement: F,
+	limit: Option<usize>,
+	every: usize,
+	remove_empty: bool,
+}
+
+impl<F: Fn(&TextMatch) -> Component> TextReplacer<F> {
+	/// Creates a new replacer that searches for a literal string.
+	pub fn literal(pattern: impl Into<String>, replacement: F) -> Self {
+		Self::new(Pattern::Literal(pattern.into()), replacement)
+	}
+
+	/// Creates a new replacer that searches for a regular expression. Requires the `regex` crate
+	/// feature.
+	///
+	/// Since matches can't span across content other than text, the expression is matched against
+	/// each run of text between such content separately. This means that `^` and `$` match at the
+	/// start and end of every run, and not only at the start and end of the whole text.
+	///
+	/// # Examples
+	/// ```
+	/// use regex::Regex;
+	/// use typewheel::{ClickEvent, Component};
+	/// use typewheel::text::TextReplacer;
+	///
+	/// let links = TextReplacer::regex(Regex::new(r"https://(\S+)").unwrap(), |found| {
+	///     Component::text(found.group(1).unwrap())
+	///         .with_underlined(true)
+	///         .with_click_event(ClickEvent::copy(found.text()))
+	/// });
+	///
+	/// assert_eq!(
+	///     links.replace(Component::text("see https://example.com")),
+	///     Component::text("see ").with_extra([Component::text("example.com")
+	///         .with_underlined(true)
+	///         .with_click_event(ClickEvent::copy("https://example.com"))])
+	/// );
+	/// ```
+	#[cfg(feature = "regex")]
+	pub fn regex(pattern: regex::Regex, replacement: F) -> Self {
+		Self::new(Pattern::Regex(pattern), replacement)
+	}
+
+	fn new(pattern: Pattern, replacement: F) -> Self {
+		Self {
+			pattern,
+			replacement,
+			limit: None,
+			every: 1,
+			remove_empty: false,
+		}
+	}
+
+	/// A builder method for limiting the number of matches that are replaced in each tree. Once
+	/// the limit is reached, further matches are left untouched. This method assumes ownership of
+	/// `self`, and passes it back when it returns.
+	#[must_use]
+	pub fn with_limit(mut self, limit: usize) -> Self {
+		self.limit = Some(limit);
+		self
+	}
+
+	/// A builder method for only replacing every nth match in each tree, starting with the nth.
+	/// Matches that are skipped still count towards this, but not towards the [limit][Self::with_limit()].
+	/// This method assumes ownership of `self`, and passes it back when it returns.
+	///
+	/// # Panics
+	/// Panics if `n` is zero.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::Component;
+	/// use typewheel::text::TextReplacer;
+	///
+	/// let replacer = TextReplacer::literal("a", |_| Component::text("b"))
+	///     .with_every(2)
+	///     .with_limit(2);
+	///
+	/// assert_eq!(
+	///     replacer.replace(Component::text("aaaaaaa")),
+	///     Component::text("a").with_extra(["b", "a", "b", "aaa"])
+	/// );
+	/// ```
+	#[must_use]
+	pub fn with_every(mut self, n: usize) -> Self {
+		assert!(n > 0, "cannot replace every 0th match");
+		self.every = n;
+		self
+	}
+
+	/// A builder method for removing nodes that are left with nothing to display, because all of
+	/// their text was covered by a match that started in another node. By default, these nodes are
+	/// kept as [empty][crate::Content::Empty] nodes, along with their style. This method assumes
+	/// ownership of `self`, and passes it back when it returns.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::Component;
+	/// use typewheel::text::TextReplacer;
+	///
+	/// let component = Component::text("a").with_extra([Component::text("b").with_bold(true)]);
+	/// let replacer = TextReplacer::literal("ab", |_| Component::text("c"));
+	///
+	/// assert_eq!(
+	///     replacer.replace(component.clone()),
+	///     Component::empty().with_extra([Component::text("c"), Component::empty().with_bold(true)])
+	/// );
+	/// assert_eq!(
+	///     replacer.with_remove_empty(true).replace(component),
+	///     Component::empty().with_extra(["c"])
+	/// );
+	/// ```
+	#[must_use]
+	pub fn with_remove_empty(mut self, remove: bool) -> Self {
+		self.remove_empty = remove;
+		self
+	}
+
+	/// Replaces matches in a component tree.
+	pub fn replace(&self, component: Component) -> Component {
+		let seen = Cell::new(0usize);
+		let replaced = Cell::new(0);
+
+		replace_ranges(component, self.remove_empty, &mut |flow: &Flow| {
+			let mut out = Vec::new();
+
+			for segment in segments(&flow.text) {
+				for (range, captures) in self.find(&flow.text, segment) {
+					if self.limit.is_some_and(|limit| replaced.get() >= limit) {
+						return out;
+					}
+
+					seen.set(seen.get() + 1);
+					if !seen.get().is_multiple_of(self.every) {
+						continue;
+					}
+
+					let Some(leaf) = flow.leaf_at(range.start) else {
+						continue;
+					};
+
+					let found = TextMatch {
+						text: &flow.text[range.clone()],
+						style: &leaf.style,
+						#[cfg(feature = "regex")]
+						captures,
+					};
+
+					#[cfg(not(feature = "regex"))]
+					let _ = captures;
+
+					let value = (self.replacement)(&found);
+					out.push((range, Placeholder::Component(value)));
+					replaced.set(replaced.get() + 1);
+				}
+			}
+
+			out
+		})
+	}
+
+	/// Finds every non-empty match in a segment of text.
+	fn find<'t>(
+		&self,
+		text: &'t str,
+		segment: Range<usize>,
+	) -> Vec<(Range<usize>, Option<Captures<'t>>)> {
+		let haystack = &text[segment.clone()];
+
+		let found: Vec<_> = match &self.pattern {
+			Pattern::Literal(literal) if literal.is_empty() => Vec::new(),
+			Pattern::Literal(literal) => haystack
+				.match_indices(literal.as_str())
+				.map(|(start, found)| (start..start + found.len(), None))
+				.collect(),
+			#[cfg(feature = "regex")]
+			Pattern::Regex(regex) => regex
+				.captures_iter(haystack)
+				.map(|captures| {
+					let range = captures.get(0).expect("group 0 always matches").range();
+					(range, Some(captures))
+				})
+				.filter(|(range, _)| !range.is_empty())
+				.collect(),
+		};
+
+		found
+			.into_iter()
+			.map(|(range, captures)| {
+				let range = segment.start + range.start..segment.start + range.end;
+				(range, captures)
+			})
+			.collect()
+	}
+}
+
+impl<Ctx: ?Sized, F: Fn(&TextMatch) -> Component> ComponentRenderer<Ctx> for TextReplacer<F> {
+	fn render(&self, component: &Component, _ctx: &Ctx) -> Component {
+		self.replace(component.clone())
+	}
+}
+
+#[cfg(feature = "regex")]
+type Captures<'t> = regex::Captures<'t>;
+
+#[cfg(not(feature = "regex"))]
+type Captures<'t> = std::marker::PhantomData<&'t str>;
+
+/// A match found by a [TextReplacer].
+#[derive(Debug)]
+pub struct TextMatch<'a> {
+	text: &'a str,
+	style: &'a Style,
+	#[cfg(feature = "regex")]
+	captures: Option<regex::Captures<'a>>,
+}
+
+impl<'a> TextMatch<'a> {
+	/// Gets the text that was matched.
+	#[inline]
+	pub fn text(&self) -> &'a str {
+		self.text
+	}
+
+	/// Gets the effective style of the text at the start of the match. This is the style that the
+	/// replacement inherits.
+	#[inline]
+	pub fn style(&self) -> &'a Style {
+		self.style
+	}
+
+	/// Gets the text matched by a capture group. Group 0 is the whole match, which is the only group
+	/// for literal patterns.
+	pub fn group(&self, index: usize) -> Option<&'a str> {
+		#[cfg(feature = "regex")]
+		if let Some(captures) = &self.captures {
+			return captures.get(index).map(|group| group.as_str());
+		}
+
+		(index == 0).then_some(self.text)
+	}
+
+	/// Gets the text matched by a named capture group. Requires the `regex` crate feature.
+	#[cfg(feature = "regex")]
+	pub fn name(&self, name: &str) -> Option<&'a str> {
+		let captures = self.captures.as_ref()?;
+		captures.name(name).map(|group| group.as_str())
+	}
+}
+
+/// Splits a flow's text into the ranges between the markers for content other than text.
+fn segments(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+	let mut start = 0;
+	text.split('\0').map(move |segment| {
+		let range = start..start + segment.len();
+		start = range.end + 1;
+		range
+	})
+}
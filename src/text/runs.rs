@@ -0,0 +1,232 @@
+use crate::iter::StyledNode;
+use crate::{Component, Content, HoverEvent, Style};
+
+/// A piece of a component's content together with its effective style. A list of runs is a flat
+/// form of a component tree that keeps everything about how the tree is displayed, but nothing
+/// about its shape. See [Component::to_runs()].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct StyledRun {
+	/// The effective style of this run, with every field inherited from parent nodes resolved.
+	pub style: Style,
+
+	/// The content of this run. This is never [Content::Empty].
+	pub content: Content,
+}
+
+impl StyledRun {
+	/// Creates a new run. Runs with [empty][Content::Empty] content are ignored by
+	/// [Component::from_runs()].
+	#[inline]
+	pub fn new(style: Style, content: Content) -> Self {
+		Self { style, content }
+	}
+
+	/// Creates a new run of text.
+	#[inline]
+	pub fn text(style: Style, text: impl Into<String>) -> Self {
+		Self::new(style, Content::Text(text.into()))
+	}
+
+	/// Gets the text of this run, if it is a run of text.
+	pub fn text_content(&self) -> Option<&str> {
+		match &self.content {
+			Content::Text(text) => Some(text),
+			_ => None,
+		}
+	}
+}
+
+impl Component {
+	/// Flattens this component into runs of content with their effective style, in display order.
+	/// Adjacent runs of text with the same style are merged, and empty nodes and text are skipped.
+	///
+	/// Runs are a canonical intermediate form of a component: two trees that display the same way
+	/// have the same runs, and [Self::from_runs()] turns them back into a tree.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::{Component, Style, TextColor};
+	/// use typewheel::text::StyledRun;
+	///
+	/// let component = Component::text("a")
+	///     .with_color(TextColor::Red)
+	///     .with_extra([Component::text("b"), Component::text("c").with_bold(true)]);
+	///
+	/// let red = Style::color(TextColor::Red);
+	/// let mut bold_red = red.clone();
+	/// bold_red.bold = Some(true);
+	///
+	/// assert_eq!(
+	///     component.to_runs(),
+	///     [StyledRun::text(red, "ab"), StyledRun::text(bold_red, "c")]
+	/// );
+	/// ```
+	pub fn to_runs(&self) -> Vec<StyledRun> {
+		let mut runs: Vec<StyledRun> = Vec::new();
+
+		for StyledNode { node, style, .. } in self.iter_styled() {
+			match (&node.content, runs.last_mut()) {
+				(Content::Empty, _) => {}
+				(Content::Text(text), _) if text.is_empty() => {}
+				(
+					Content::Text(text),
+					Some(StyledRun {
+						style: last_style,
+						content: Content::Text(last),
+					}),
+				) if *last_style == style => last.push_str(text),
+				(content, _) => runs.push(StyledRun::new(style, content.clone())),
+			}
+		}
+
+		runs
+	}
+
+	/// Builds a compact component tree from runs. Each run is nested under the run before it
+	/// wherever its style allows, so that styles shared between runs are only written once. The
+	/// result is then [compacted][Self::compact()].
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::{Component, TextColor};
+	///
+	/// let component = Component::empty().with_extra([
+	///     Component::text("a").with_color(TextColor::Red),
+	///     Component::empty().with_extra([Component::text("b").with_color(TextColor::Red)]),
+	///     Component::text("c").with_color(TextColor::Red).with_bold(true),
+	/// ]);
+	///
+	/// assert_eq!(
+	///     Component::from_runs(component.to_runs()),
+	///     Component::text("ab")
+	///         .with_color(TextColor::Red)
+	///         .with_extra([Component::text("c").with_bold(true)])
+	/// );
+	/// ```
+	pub fn from_runs(runs: impl IntoIterator<Item = StyledRun>) -> Self {
+		// Nodes that can still receive children, with their effective styles.
+		let mut open: Vec<(Component, Style)> = Vec::new();
+		let mut roots = Vec::new();
+
+		for StyledRun { style, content } in runs {
+			if let Content::Empty = content {
+				continue;
+			}
+
+			// A run can only be nested under a node if it overrides every field of that node's
+			// style; otherwise, it would inherit fields that it doesn't have.
+			while let Some((_, parent)) = open.last() {
+				if style.covers(parent) {
+					break;
+				}
+
+				close(&mut open, &mut roots);
+			}
+
+			let mut own = style.clone();
+			if let Some((_, parent)) = open.last() {
+				own.strip_inherited(parent);
+			}
+
+			open.push((Component::create_flat(content, own), style));
+		}
+
+		while !open.is_empty() {
+			close(&mut open, &mut roots);
+		}
+
+		Component::create(Content::Empty, Style::BLANK, roots).compact()
+	}
+}
+
+impl Component {
+	/// Checks if this component displays the same way as another. Unlike `==`, which compares the
+	/// structure of two trees, this compares their [runs][Self::to_runs()], so it does not matter
+	/// how the text is split into nodes or where styles are set. Translation arguments and hover
+	/// text are compared the same way.
+	///
+	/// Style fields that are set explicitly are still considered different from unset fields, even
+	/// if they are set to the value the client would use by default.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::{Component, TextColor};
+	///
+	/// let a = Component::text("a").with_extra(["b"]);
+	/// let b = Component::text("ab");
+	///
+	/// assert_ne!(a, b);
+	/// assert!(a.renders_same_as(&b));
+	/// assert!(!a.renders_same_as(&b.with_color(TextColor::Red)));
+	/// ```
+	pub fn renders_same_as(&self, other: &Component) -> bool {
+		canonical_runs(self) == canonical_runs(other)
+	}
+
+	/// Converts this component into its canonical form. Any two components that
+	/// [render the same way][Self::renders_same_as()] have the same canonical form, so it can be
+	/// used to deduplicate components or compare them with `==`. The canonical form is built with
+	/// [Self::from_runs()], so it is also compact.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::{Component, TextColor};
+	///
+	/// let a = Component::text("a")
+	///     .with_color(TextColor::Red)
+	///     .with_extra(["b"]);
+	/// let b = Component::empty().with_extra([
+	///     Component::text("ab").with_color(TextColor::Red),
+	///     Component::text(""),
+	/// ]);
+	///
+	/// assert_eq!(a.canonicalize(), b.canonicalize());
+	/// ```
+	#[must_use]
+	pub fn canonicalize(&self) -> Self {
+		Self::from_runs(canonical_runs(self))
+	}
+}
+
+/// Gets the runs of a component with all nested trees in canonical form.
+fn canonical_runs(component: &Component) -> Vec<StyledRun> {
+	let mut runs: Vec<StyledRun> = Vec::new();
+
+	for mut run in component.to_runs() {
+		if let Content::Translation { with, .. } = &mut run.content {
+			for arg in with {
+				*arg = arg.canonicalize();
+			}
+		}
+
+		match &mut run.style.hover_event {
+			Some(HoverEvent::ShowText(text)) => **text = text.canonicalize(),
+			Some(HoverEvent::ShowEntity(entity)) => entity.name = entity.name.canonicalize(),
+			_ => {}
+		}
+
+		// Runs with hover text of different shapes can only be merged once it is canonical.
+		match (runs.last_mut(), &run.content) {
+			(
+				Some(StyledRun {
+					style,
+					content: Content::Text(last),
+				}),
+				Content::Text(text),
+			) if *style == run.style => last.push_str(text),
+			_ => runs.push(run),
+		}
+	}
+
+	runs
+}
+
+/// Finishes the innermost open node, adding it to its parent or the list of roots.
+fn close(open: &mut Vec<(Component, Style)>, roots: &mut Vec<Component>) {
+	let (node, _) = open.pop().expect("there is an open node");
+	match open.last_mut() {
+		Some((parent, _)) => parent.extra.push(node),
+		None => roots.push(node),
+	}
+}
@@ -0,0 +1,126 @@
+use super::TextUnit;
+use crate::codec::{ComponentCodec, PlainTextCodec};
+use crate::{Component, Content};
+use std::ops::{Bound, Range, RangeBounds};
+
+impl Component {
+	/// Creates a new component containing only the text in the provided range of character
+	/// offsets. See [Self::slice_by()] for details.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::{Component, TextColor};
+	///
+	/// let component = Component::text("hello ")
+	///     .with_extra([Component::text("world").with_color(TextColor::Red)]);
+	///
+	/// assert_eq!(
+	///     component.slice(3..8),
+	///     Component::text("lo ").with_extra([Component::text("wo").with_color(TextColor::Red)])
+	/// );
+	/// ```
+	#[inline]
+	pub fn slice(&self, range: impl RangeBounds<usize>) -> Self {
+		self.slice_by(range, TextUnit::Chars)
+	}
+
+	/// Creates a new component containing only the text in the provided range, counted in the
+	/// provided [unit][TextUnit]. Offsets are positions in the [plain text][crate::text] of this
+	/// component, and are clamped to its length.
+	///
+	/// The shape of the tree is kept: nodes are trimmed to the range, and nodes that end up with
+	/// nothing in the range are removed. Every remaining fragment keeps the style and events of
+	/// the nodes it was in. Content other than text is kept if its position falls in the range.
+	pub fn slice_by(&self, range: impl RangeBounds<usize>, unit: TextUnit) -> Self {
+		let text = PlainTextCodec.serialize(self);
+
+		let start = match range.start_bound() {
+			Bound::Included(&start) => unit.byte_offset(&text, start),
+			Bound::Excluded(&start) => unit.byte_offset(&text, start.saturating_add(1)),
+			Bound::Unbounded => 0,
+		};
+		let end = match range.end_bound() {
+			Bound::Included(&end) => unit.byte_offset(&text, end.saturating_add(1)),
+			Bound::Excluded(&end) => unit.byte_offset(&text, end),
+			Bound::Unbounded => text.len(),
+		};
+
+		let range = start.min(end)..end;
+		let include_end = end == text.len();
+		slice_bytes(self, &mut 0, &range, include_end).unwrap_or_default()
+	}
+
+	/// Splits this component in two at a character offset. See [Self::split_at_by()] for details.
+	///
+	/// # Examples
+	/// ```
+	/// use typewheel::Component;
+	///
+	/// let component = Component::text("ab").with_extra(["cd"]);
+	/// let (left, right) = component.split_at(3);
+	///
+	/// assert_eq!(left, Component::text("ab").with_extra(["c"]));
+	/// assert_eq!(right, Component::empty().with_extra(["d"]));
+	/// ```
+	#[inline]
+	pub fn split_at(&self, index: usize) -> (Self, Self) {
+		self.split_at_by(index, TextUnit::Chars)
+	}
+
+	/// Splits this component in two at an offset counted in the provided [unit][TextUnit]. The
+	/// first component holds the text before the offset, and the second holds the rest. Content
+	/// other than text that sits exactly at the offset goes into the second component.
+	///
+	/// Both halves are created the same way as with [Self::slice_by()].
+	pub fn split_at_by(&self, index: usize, unit: TextUnit) -> (Self, Self) {
+		let text = PlainTextCodec.serialize(self);
+		let index = unit.byte_offset(&text, index);
+
+		let left = slice_bytes(self, &mut 0, &(0..index), false);
+		let right = slice_bytes(self, &mut 0, &(index..text.len()), true);
+
+		(left.unwrap_or_default(), right.unwrap_or_default())
+	}
+}
+
+/// Slices a tree to a byte range of its plain text. `offset` tracks the plain text position of
+/// the current node. Non-text content at the end of the range is only kept if `include_end` is
+/// set. Returns [None] if nothing in this node is in range.
+fn slice_bytes(
+	node: &Component,
+	offset: &mut usize,
+	range: &Range<usize>,
+	include_end: bool,
+) -> Option<Component> {
+	let position = *offset;
+	let content = match &node.content {
+		Content::Text(text) => {
+			*offset += text.len();
+
+			let start = range.start.clamp(position, *offset) - position;
+			let end = range.end.clamp(position, *offset) - position;
+			(start < end).then(|| Content::Text(text[start..end].to_owned()))
+		}
+		Content::Empty => None,
+		content => {
+			let in_range = range.contains(&position) || (include_end && position == range.end);
+			(range.start <= position && in_range).then(|| content.clone())
+		}
+	};
+
+	let extra: Vec<_> = node
+		.extra
+		.iter()
+		.filter_map(|child| slice_bytes(child, offset, range, include_end))
+		.collect();
+
+	if content.is_none() && extra.is_empty() {
+		return None;
+	}
+
+	Some(Component::create(
+		content.unwrap_or_default(),
+		node.style.clone(),
+		extra,
+	))
+}
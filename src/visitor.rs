@@ -0,0 +1,397 @@
+//! A module for traversing and rebuilding component trees with one callback per kind of value.
+//!
+//! A [ComponentVisitor] walks a tree by reference, and a [ComponentFolder] consumes a tree and
+//! rebuilds it. Both traits have a method for every [content][Content] type and every style field
+//! that holds data, and every method has a default implementation that carries on the walk, so
+//! implementors only need to override the parts they care about. The default recursion is also
+//! exposed as free functions (such as [walk_component()] and [fold_component()]), so that
+//! overriding methods can still fall back to it.
+//!
+//! The default walks destructure every content type and style field without wildcards, so adding a
+//! new one is a compile error here rather than a silently skipped case in every transform.
+//!
+//! # Examples
+//! ```
+//! use typewheel::Component;
+//! use typewheel::visitor::ComponentVisitor;
+//!
+//! #[derive(Default)]
+//! struct KeybindCollector(Vec<String>);
+//!
+//! impl ComponentVisitor for KeybindCollector {
+//!     fn visit_keybind(&mut self, key: &str) {
+//!         self.0.push(key.to_owned());
+//!     }
+//! }
+//!
+//! let component = Component::text("Press ").with_extra([
+//!     Component::keybind("key.jump"),
+//!     Component::text(" or "),
+//!     Component::keybind("key.sneak"),
+//! ]);
+//!
+//! let mut collector = KeybindCollector::default();
+//! collector.visit_component(&component);
+//! assert_eq!(collector.0, ["key.jump", "key.sneak"]);
+//! ```
+
+use crate::render::VirtualContent;
+use crate::{ClickEvent, Component, Content, HoverEvent, Style, TextColor};
+
+/// A trait for walking a component tree by reference. See the [module docs][self] for more
+/// information.
+///
+/// By default, [Self::visit_component()] visits a node's style, then its content, then its
+/// children, which is the order that they are serialized in. Translation arguments, hover text,
+/// hovered entity names and virtual fallbacks are visited as components too.
+pub trait ComponentVisitor {
+	/// Visits a component and everything nested in it. Overriding this method replaces the default
+	/// recursion entirely; see [walk_component()].
+	fn visit_component(&mut self, component: &Component) {
+		walk_component(self, component);
+	}
+
+	/// Visits a node's content. The default implementation calls the hook for its content type.
+	fn visit_content(&mut self, content: &Content) {
+		walk_content(self, content);
+	}
+
+	/// Visits a [text][Content::Text] node's content.
+	fn visit_text(&mut self, _text: &str) {}
+
+	/// Visits a [translation][Content::Translation] node's content. The default implementation
+	/// visits each of the translation's arguments.
+	fn visit_translation(&mut self, _key: &str, with: &[Component]) {
+		for arg in with {
+			self.visit_component(arg);
+		}
+	}
+
+	/// Visits a [keybind][Content::Keybind] node's content.
+	fn visit_keybind(&mut self, _key: &str) {}
+
+	/// Visits a [score][Content::Score] node's content.
+	fn visit_score(&mut self, _name: &str, _objective: &str, _value: &str) {}
+
+	/// Visits a [virtual][Content::Virtual] node's content. The default implementation visits its
+	/// fallback, if it has one.
+	fn visit_virtual(&mut self, content: &VirtualContent) {
+		if let Some(fallback) = content.fallback() {
+			self.visit_component(fallback);
+		}
+	}
+
+	/// Visits an [empty][Content::Empty] node's content.
+	fn visit_empty(&mut self) {}
+
+	/// Visits a node's style. The default implementation calls the hook for every field that is
+	/// set and holds data; see [walk_style()].
+	fn visit_style(&mut self, style: &Style) {
+		walk_style(self, style);
+	}
+
+	/// Visits a node's font.
+	fn visit_font(&mut self, _font: &str) {}
+
+	/// Visits a node's color.
+	fn visit_color(&mut self, _color: &TextColor) {}
+
+	/// Visits a node's insertion text.
+	fn visit_insertion(&mut self, _insertion: &str) {}
+
+	/// Visits a node's click event.
+	fn visit_click_event(&mut self, _event: &ClickEvent) {}
+
+	/// Visits a node's hover event. The default implementation visits the text of
+	/// [ShowText][HoverEvent::ShowText] events and the name of [ShowEntity][HoverEvent::ShowEntity]
+	/// events.
+	fn visit_hover_event(&mut self, event: &HoverEvent) {
+		walk_hover_event(self, event);
+	}
+}
+
+/// The default tree walk used by [ComponentVisitor::visit_component()].
+pub fn walk_component<V: ComponentVisitor + ?Sized>(visitor: &mut V, component: &Component) {
+	let Component {
+		content,
+		style,
+		extra,
+	} = component;
+
+	visitor.visit_style(style);
+	visitor.visit_content(content);
+
+	for child in extra {
+		visitor.visit_component(child);
+	}
+}
+
+/// The default content dispatch used by [ComponentVisitor::visit_content()].
+pub fn walk_content<V: ComponentVisitor + ?Sized>(visitor: &mut V, content: &Content) {
+	match content {
+		Content::Text(text) => visitor.visit_text(text),
+		Content::Translation { key, with } => visitor.visit_translation(key, with),
+		Content::Keybind(key) => visitor.visit_keybind(key),
+		Content::Score {
+			name,
+			objective,
+			value,
+		} => visitor.visit_score(name, objective, value),
+		Content::Virtual(content) => visitor.visit_virtual(content),
+		Content::Empty => visitor.visit_empty(),
+	}
+}
+
+/// The default style walk used by [ComponentVisitor::visit_style()]. Formatting flags such as
+/// `bold` have no hooks of their own, and can be read from the style directly.
+pub fn walk_style<V: ComponentVisitor + ?Sized>(visitor: &mut V, style: &Style) {
+	let Style {
+		bold: _,
+		italic: _,
+		underlined: _,
+		strikethrough: _,
+		obfuscated: _,
+		font,
+		color,
+		insertion,
+		click_event,
+		hover_event,
+	} = style;
+
+	if let Some(font) = font {
+		visitor.visit_font(font);
+	}
+
+	if let Some(color) = color {
+		visitor.visit_color(color);
+	}
+
+	if let Some(insertion) = insertion {
+		visitor.visit_insertion(insertion);
+	}
+
+	if let Some(event) = click_event {
+		visitor.visit_click_event(event);
+	}
+
+	if let Some(event) = hover_event {
+		visitor.visit_hover_event(event);
+	}
+}
+
+/// The default hover event walk used by [ComponentVisitor::visit_hover_event()].
+pub fn walk_hover_event<V: ComponentVisitor + ?Sized>(visitor: &mut V, event: &HoverEvent) {
+	match event {
+		HoverEvent::ShowText(text) => visitor.visit_component(text),
+		HoverEvent::ShowEntity(entity) => visitor.visit_component(&entity.name),
+		HoverEvent::ShowItem(_) => {}
+	}
+}
+
+/// A trait for consuming a component tree and rebuilding it. See the [module docs][self] for more
+/// information.
+///
+/// By default, [Self::fold_component()] folds a node's style, content and children, and puts them
+/// back together. The content hooks return new [Content], so they can change a node's content
+/// type, and the style field hooks return an [Option], so they can clear the field.
+///
+/// # Examples
+/// ```
+/// use typewheel::{Component, Content, TextColor};
+/// use typewheel::visitor::ComponentFolder;
+///
+/// struct Shout;
+///
+/// impl ComponentFolder for Shout {
+///     fn fold_text(&mut self, text: String) -> Content {
+///         Content::Text(text.to_uppercase())
+///     }
+///
+///     fn fold_color(&mut self, _color: TextColor) -> Option<TextColor> {
+///         Some(TextColor::Red)
+///     }
+/// }
+///
+/// let component = Component::text("hey ")
+///     .with_color(TextColor::Gray)
+///     .with_extra(["listen"]);
+///
+/// assert_eq!(
+///     Shout.fold_component(component),
+///     Component::text("HEY ")
+///         .with_color(TextColor::Red)
+///         .with_extra(["LISTEN"])
+/// );
+/// ```
+pub trait ComponentFolder {
+	/// Folds a component and everything nested in it. Overriding this method replaces the default
+	/// recursion entirely; see [fold_component()].
+	fn fold_component(&mut self, component: Component) -> Component {
+		fold_component(self, component)
+	}
+
+	/// Folds a node's content. The default implementation calls the hook for its content type.
+	fn fold_content(&mut self, content: Content) -> Content {
+		fold_content(self, content)
+	}
+
+	/// Folds a [text][Content::Text] node's content.
+	fn fold_text(&mut self, text: String) -> Content {
+		Content::Text(text)
+	}
+
+	/// Folds a [translation][Content::Translation] node's content. The default implementation
+	/// folds each of the translation's arguments.
+	fn fold_translation(&mut self, key: String, with: Vec<Component>) -> Content {
+		Content::Translation {
+			key,
+			with: with
+				.into_iter()
+				.map(|arg| self.fold_component(arg))
+				.collect(),
+		}
+	}
+
+	/// Folds a [keybind][Content::Keybind] node's content.
+	fn fold_keybind(&mut self, key: String) -> Content {
+		Content::Keybind(key)
+	}
+
+	/// Folds a [score][Content::Score] node's content.
+	fn fold_score(&mut self, name: String, objective: String, value: String) -> Content {
+		Content::Score {
+			name,
+			objective,
+			value,
+		}
+	}
+
+	/// Folds a [virtual][Content::Virtual] node's content. The default implementation keeps the
+	/// content as is, including its fallback.
+	fn fold_virtual(&mut self, content: VirtualContent) -> Content {
+		Content::Virtual(content)
+	}
+
+	/// Folds an [empty][Content::Empty] node's content.
+	fn fold_empty(&mut self) -> Content {
+		Content::Empty
+	}
+
+	/// Folds a node's style. The default implementation calls the hook for every field that is set
+	/// and holds data; see [fold_style()].
+	fn fold_style(&mut self, style: Style) -> Style {
+		fold_style(self, style)
+	}
+
+	/// Folds a node's font.
+	fn fold_font(&mut self, font: String) -> Option<String> {
+		Some(font)
+	}
+
+	/// Folds a node's color.
+	fn fold_color(&mut self, color: TextColor) -> Option<TextColor> {
+		Some(color)
+	}
+
+	/// Folds a node's insertion text.
+	fn fold_insertion(&mut self, insertion: String) -> Option<String> {
+		Some(insertion)
+	}
+
+	/// Folds a node's click event.
+	fn fold_click_event(&mut self, event: ClickEvent) -> Option<ClickEvent> {
+		Some(event)
+	}
+
+	/// Folds a node's hover event. The default implementation folds the text of
+	/// [ShowText][HoverEvent::ShowText] events and the name of [ShowEntity][HoverEvent::ShowEntity]
+	/// events.
+	fn fold_hover_event(&mut self, event: HoverEvent) -> Option<HoverEvent> {
+		Some(fold_hover_event(self, event))
+	}
+}
+
+/// The default tree fold used by [ComponentFolder::fold_component()].
+pub fn fold_component<F: ComponentFolder + ?Sized>(
+	folder: &mut F,
+	component: Component,
+) -> Component {
+	let Component {
+		content,
+		style,
+		extra,
+	} = component;
+
+	let style = folder.fold_style(style);
+	let content = folder.fold_content(content);
+	let extra = extra
+		.into_iter()
+		.map(|child| folder.fold_component(child))
+		.collect();
+
+	Component::create(content, style, extra)
+}
+
+/// The default content dispatch used by [ComponentFolder::fold_content()].
+pub fn fold_content<F: ComponentFolder + ?Sized>(folder: &mut F, content: Content) -> Content {
+	match content {
+		Content::Text(text) => folder.fold_text(text),
+		Content::Translation { key, with } => folder.fold_translation(key, with),
+		Content::Keybind(key) => folder.fold_keybind(key),
+		Content::Score {
+			name,
+			objective,
+			value,
+		} => folder.fold_score(name, objective, value),
+		Content::Virtual(content) => folder.fold_virtual(content),
+		Content::Empty => folder.fold_empty(),
+	}
+}
+
+/// The default style fold used by [ComponentFolder::fold_style()]. Formatting flags such as `bold`
+/// are copied as is.
+pub fn fold_style<F: ComponentFolder + ?Sized>(folder: &mut F, style: Style) -> Style {
+	let Style {
+		bold,
+		italic,
+		underlined,
+		strikethrough,
+		obfuscated,
+		font,
+		color,
+		insertion,
+		click_event,
+		hover_event,
+	} = style;
+
+	Style {
+		bold,
+		italic,
+		underlined,
+		strikethrough,
+		obfuscated,
+		font: font.and_then(|font| folder.fold_font(font)),
+		color: color.and_then(|color| folder.fold_color(color)),
+		insertion: insertion.and_then(|insertion| folder.fold_insertion(insertion)),
+		click_event: click_event.and_then(|event| folder.fold_click_event(event)),
+		hover_event: hover_event.and_then(|event| folder.fold_hover_event(event)),
+	}
+}
+
+/// The default hover event fold used by [ComponentFolder::fold_hover_event()].
+pub fn fold_hover_event<F: ComponentFolder + ?Sized>(
+	folder: &mut F,
+	event: HoverEvent,
+) -> HoverEvent {
+	match event {
+		HoverEvent::ShowText(mut text) => {
+			*text = folder.fold_component(*text);
+			HoverEvent::ShowText(text)
+		}
+		HoverEvent::ShowEntity(mut entity) => {
+			entity.name = folder.fold_component(entity.name);
+			HoverEvent::ShowEntity(entity)
+		}
+		HoverEvent::ShowItem(item) => HoverEvent::ShowItem(item),
+	}
+}
@@ -0,0 +1,94 @@
+use typewheel::{Component, HoverEvent, TextColor};
+
+mod common;
+
+#[test]
+fn compacts_redundant_styles() {
+	let component = Component::text("a").with_color(TextColor::Red).with_extra([
+		Component::text("b").with_color(TextColor::Red),
+		Component::text("c")
+			.with_color(TextColor::Red)
+			.with_bold(true),
+	]);
+
+	assert_eq!(
+		component.compact(),
+		Component::text("ab")
+			.with_color(TextColor::Red)
+			.with_extra([Component::text("c").with_bold(true)])
+	);
+}
+
+#[test]
+fn hoists_shared_styles() {
+	let component = Component::empty().with_color(TextColor::Blue).with_extra([
+		Component::text("a")
+			.with_color(TextColor::Red)
+			.with_italic(true),
+		Component::keybind("key.jump").with_color(TextColor::Red),
+		Component::empty(),
+	]);
+
+	assert_eq!(
+		component.compact(),
+		Component::empty().with_color(TextColor::Red).with_extra([
+			Component::text("a").with_italic(true),
+			Component::keybind("key.jump"),
+		])
+	);
+}
+
+#[test]
+fn unwraps_empty_nodes() {
+	let component = Component::empty().with_extra([
+		Component::empty().with_extra([Component::empty().with_extra(["a", "b"])]),
+		Component::empty().with_bold(true).with_extra(["c"]),
+	]);
+
+	assert_eq!(
+		component.compact(),
+		Component::text("ab").with_extra([Component::text("c").with_bold(true)])
+	);
+
+	assert_eq!(Component::empty().compact(), Component::empty());
+}
+
+#[test]
+fn compacts_nested_trees() {
+	let component = Component::translate(
+		"chat.type.text",
+		[Component::empty().with_extra(["a", "b"])],
+	)
+	.with_hover_event(HoverEvent::show_text(
+		Component::empty().with_extra([Component::text("c")]),
+	));
+
+	assert_eq!(
+		component.compact(),
+		Component::translate("chat.type.text", [Component::text("ab")])
+			.with_hover_event(HoverEvent::show_text("c"))
+	);
+
+	assert_eq!(common::styled_hello().compact(), common::styled_hello());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn shrinks_json() {
+	use typewheel::codec::{ComponentCodec, JsonCodec};
+
+	let component = Component::empty().with_extra("hello world".chars().map(|c| {
+		Component::text(c)
+			.with_color(TextColor::Gold)
+			.with_bold(true)
+	}));
+
+	let before = JsonCodec.serialize(&component).unwrap();
+	let after = JsonCodec.serialize(&component.compact()).unwrap();
+
+	assert_eq!(
+		after,
+		r#"{"bold":true,"color":"gold","text":"hello world"}"#
+	);
+	assert!(after.len() < before.len());
+}
@@ -0,0 +1,187 @@
+use typewheel::font::{DefaultFontMetrics, FontMetrics, Glyph};
+use typewheel::{Component, Key};
+
+mod common;
+
+/// A font where every glyph is 10 pixels wide.
+struct Wide;
+
+impl FontMetrics for Wide {
+	fn glyph(&self, font: &Key, _: char) -> Option<Glyph> {
+		(font == &Key::new("test", "wide")).then_some(Glyph::new(10.0))
+	}
+}
+
+#[test]
+fn default_font_width() {
+	// "hello " and "world" are both bold, since the child inherits its parent's style.
+	let component = common::styled_hello();
+	assert_eq!(
+		component.width(&DefaultFontMetrics),
+		(28.0 + 6.0) + (27.0 + 5.0)
+	);
+
+	let inherited = Component::empty()
+		.with_bold(true)
+		.with_extra([Component::text("ab"), Component::text("c").with_bold(false)]);
+	assert_eq!(inherited.width(&DefaultFontMetrics), 14.0 + 6.0);
+
+	// Characters that no provider knows get the fallback advance, plus the bold offset.
+	let unknown = Component::text("é");
+	assert_eq!(unknown.width(&DefaultFontMetrics), 6.0);
+	assert_eq!(unknown.with_bold(true).width(&DefaultFontMetrics), 7.0);
+}
+
+#[test]
+fn custom_fonts() {
+	let metrics = Wide.or(DefaultFontMetrics);
+	let component = Component::text("ab")
+		.with_font("test:wide")
+		.with_extra([Component::text("c").with_font("minecraft:default")]);
+
+	assert_eq!(component.width(&metrics), 26.0);
+
+	// Unknown fonts fall back to the default font metrics.
+	assert_eq!(component.width(&DefaultFontMetrics), 18.0);
+}
+
+#[test]
+fn alignment() {
+	let component = Component::text("abcd");
+
+	let right = component.clone().aligned_right(40.0, &DefaultFontMetrics);
+	assert_eq!(right.extra[0], Component::text("    "));
+	assert_eq!(right.width(&DefaultFontMetrics), 40.0);
+
+	let centered = component.clone().centered(40.0, &DefaultFontMetrics);
+	assert_eq!(centered.extra[0], Component::text("  "));
+
+	// Components wider than the line are left alone.
+	assert_eq!(
+		component.clone().centered(10.0, &DefaultFontMetrics),
+		component
+	);
+}
+
+#[test]
+#[cfg(feature = "resource-pack")]
+fn resource_pack_fonts() {
+	use typewheel::font::{FontLoadError, ResourcePackMetrics};
+
+	// A 16x8 texture with two 8x8 cells. The first glyph is 3 pixels wide, the second is empty.
+	let mut texture = Vec::new();
+	{
+		let mut encoder = png::Encoder::new(&mut texture, 16, 8);
+		encoder.set_color(png::ColorType::Rgba);
+		let mut pixels = vec![0u8; 16 * 8 * 4];
+		for y in 2..6 {
+			for x in 0..3 {
+				pixels[(y * 16 + x) * 4 + 3] = 255;
+			}
+		}
+		let mut writer = encoder.write_header().unwrap();
+		writer.write_image_data(&pixels).unwrap();
+	}
+
+	let mut metrics = ResourcePackMetrics::new();
+	metrics
+		.add_font(
+			Key::new("test", "icons"),
+			br#"{"providers": [
+				{"type": "space", "advances": {" ": 3, "\u200c": -2}},
+				{"type": "bitmap", "file": "test:font/icons.png", "height": 16, "ascent": 7,
+					"chars": ["\ue000\ue001"]},
+				{"type": "reference", "id": "test:other"},
+				{"type": "ttf", "file": "test:font/font.ttf"}
+			]}"#,
+			|texture_key| {
+				assert_eq!(texture_key, &Key::new("test", "font/icons.png"));
+				Ok(texture.clone())
+			},
+		)
+		.unwrap();
+	metrics
+		.add_font(
+			Key::new("test", "other"),
+			br#"{"providers": [{"type": "space", "advances": {"x": 9}}]}"#,
+			|_| unreachable!(),
+		)
+		.unwrap();
+
+	let font = Key::new("test", "icons");
+	// Doubled in height, so the 3 pixel glyph is scaled to 6 pixels, plus 1 for spacing.
+	assert_eq!(metrics.glyph(&font, '\u{E000}'), Some(Glyph::new(7.0)));
+	assert_eq!(metrics.glyph(&font, '\u{E001}'), Some(Glyph::new(1.0)));
+	assert_eq!(metrics.glyph(&font, ' '), Some(Glyph::space(3.0)));
+	assert_eq!(metrics.glyph(&font, 'x'), Some(Glyph::space(9.0)));
+	assert_eq!(metrics.glyph(&font, 'y'), None);
+
+	let component = Component::text("\u{E000}\u{200C} x")
+		.with_font("test:icons")
+		.with_bold(true);
+	assert_eq!(component.width(&metrics), 8.0 - 2.0 + 3.0 + 9.0);
+
+	// Nine rows don't fit in an 8 pixel tall texture.
+	let result = metrics.add_font(
+		Key::new("test", "tall"),
+		format!(
+			r#"{{"providers": [{{"type": "bitmap", "file": "test:font/icons.png", "ascent": 7,
+				"chars": [{}]}}]}}"#,
+			[r#""ab""#; 9].join(", ")
+		)
+		.as_bytes(),
+		|_| Ok(texture.clone()),
+	);
+	assert!(matches!(result, Err(FontLoadError::InvalidFont(_))));
+}
+
+#[test]
+fn wrapping() {
+	use typewheel::font::Monospace;
+	use typewheel::{ClickEvent, TextColor};
+
+	let click = ClickEvent::run_command("/help");
+	let component = Component::text("aaa bbbbbbbbbb ")
+		.with_click_event(click.clone())
+		.with_extra([Component::text("c\nd").with_color(TextColor::Red)]);
+
+	assert_eq!(
+		component.wrap(6.0, &Monospace),
+		[
+			Component::text("aaa").with_click_event(click.clone()),
+			Component::text("bbbbbb").with_click_event(click.clone()),
+			Component::empty().with_extra([
+				Component::text("bbbb ").with_click_event(click.clone()),
+				Component::text("c")
+					.with_click_event(click.clone())
+					.with_color(TextColor::Red),
+			]),
+			Component::text("d")
+				.with_click_event(click)
+				.with_color(TextColor::Red),
+		]
+	);
+}
+
+#[test]
+fn pixel_wrapping() {
+	// "ab cd" is 6 + 6 + 4 + 6 + 6 pixels wide, so it only fits in one line from 28 pixels.
+	let component = Component::text("ab cd");
+
+	assert_eq!(
+		component.wrap(28.0, &DefaultFontMetrics),
+		std::slice::from_ref(&component)
+	);
+	assert_eq!(
+		component.wrap(27.0, &DefaultFontMetrics),
+		[Component::text("ab"), Component::text("cd")]
+	);
+	assert_eq!(
+		Component::text("a\n\nb").lines(),
+		[
+			Component::text("a"),
+			Component::empty(),
+			Component::text("b")
+		]
+	);
+}
@@ -0,0 +1,67 @@
+use typewheel::{component, format_component, Component, TextColor};
+
+#[test]
+fn literal_only() {
+	assert_eq!(format_component!("hello"), Component::text("hello"));
+	assert_eq!(format_component!("{{braces}}"), Component::text("{braces}"));
+}
+
+#[test]
+fn positional_and_named() {
+	let name = String::from("Notch");
+	let component = format_component!("{} has {count} {}", name, "diamonds", count = 64);
+
+	assert_eq!(
+		component,
+		Component::empty().with_extra([
+			Component::text("Notch"),
+			Component::text(" has "),
+			Component::text("64"),
+			Component::text(" "),
+			Component::text("diamonds"),
+		])
+	);
+}
+
+#[test]
+fn styled_slots() {
+	let rank = Component::text("[Admin]").with_color(TextColor::Red);
+	let component = component!(
+		"{rank:gold,bold,!italic} {name:#FFAA00}",
+		rank = rank,
+		name = "Notch",
+	);
+
+	assert_eq!(
+		component,
+		Component::empty().with_extra([
+			Component::text("[Admin]")
+				.with_color(TextColor::Red)
+				.with_bold(true)
+				.with_italic(false),
+			Component::text(" "),
+			Component::text("Notch").with_color(TextColor::Hex(0xFFAA00)),
+		])
+	);
+
+	// A single slot doesn't need a wrapping node.
+	assert_eq!(
+		component!("{:underlined}", "x"),
+		Component::text("x").with_underlined(true)
+	);
+}
+
+#[test]
+fn repeated_slots() {
+	let name = Component::text("Notch").with_color(TextColor::Gold);
+	assert_eq!(
+		format_component!("{name}{{{name:bold}}}", name = name.clone()),
+		Component::empty().with_extra([
+			name.clone(),
+			Component::text("{"),
+			name.with_bold(true),
+			Component::text("}"),
+		])
+	);
+	assert_eq!(format_component!(""), Component::empty());
+}
@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+use typewheel::intern::Interner;
+use typewheel::shared::SharedComponent;
+use typewheel::{Component, HoverEvent, TextColor};
+
+mod common;
+
+#[test]
+fn hash_matches_eq() {
+	let mut set = HashSet::new();
+	set.insert(common::styled_hello());
+	set.insert(common::styled_hello());
+	set.insert(
+		Component::text("hover").with_hover_event(HoverEvent::show_text(common::styled_hello())),
+	);
+
+	assert_eq!(set.len(), 2);
+	assert!(set.contains(&common::styled_hello()));
+	assert!(!set.contains(&common::styled_hello().with_color(TextColor::Red)));
+}
+
+#[test]
+fn interning() {
+	let mut interner = Interner::new();
+
+	let a = interner.intern(common::styled_hello());
+	let b = interner.intern(common::styled_hello());
+	let c = interner.intern(common::deeply_nested());
+
+	assert!(SharedComponent::ptr_eq(&a, &b));
+	assert_eq!(a, b);
+	assert_ne!(a, c);
+	assert_eq!(a.to_component(), common::styled_hello());
+	assert_eq!(interner.len(), 10);
+
+	assert!(SharedComponent::ptr_eq(
+		&interner.get(&common::deeply_nested()).unwrap(),
+		&c
+	));
+	assert!(interner
+		.get(&common::deeply_nested().with_extra(["x"]))
+		.is_none());
+
+	drop((a, b));
+	interner.collect_unused();
+	assert_eq!(interner.len(), 8);
+	assert_eq!(Component::from(c), common::deeply_nested());
+}
+
+#[test]
+fn shared_subtrees() {
+	let mut interner = Interner::new();
+
+	let a = interner.intern(Component::text("a").with_extra([common::deeply_nested()]));
+	let b = interner.intern(
+		Component::text("b")
+			.with_extra(["c", "d"])
+			.with_extra([common::deeply_nested()]),
+	);
+
+	assert!(SharedComponent::ptr_eq(&a.extra()[0], &b.extra()[2]));
+	assert!(SharedComponent::ptr_eq(
+		&a.extra()[0].extra()[0].extra()[1],
+		&b.extra()[1]
+	));
+	assert_eq!(interner.len(), 10);
+
+	drop(a);
+	interner.collect_unused();
+	assert_eq!(interner.len(), 9);
+	assert_eq!(
+		Component::from(b),
+		Component::text("b")
+			.with_extra(["c", "d"])
+			.with_extra([common::deeply_nested()])
+	);
+}
+
+#[test]
+fn nested_interning() {
+	let mut interner = Interner::new();
+
+	let a = interner.intern(
+		Component::text("a").with_hover_event(HoverEvent::show_text(common::styled_hello())),
+	);
+	let b = interner.intern(Component::translate("b", [common::styled_hello()]));
+	let c = interner.intern(common::styled_hello());
+
+	assert!(SharedComponent::ptr_eq(a.hover_text().unwrap(), &c));
+	assert!(SharedComponent::ptr_eq(&b.args()[0], &c));
+	assert_eq!(interner.len(), 4);
+	assert_eq!(
+		interner.get(&Component::translate("b", [common::styled_hello()])),
+		Some(b)
+	);
+}
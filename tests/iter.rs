@@ -34,3 +34,348 @@ fn breadth_first_iter() {
 
 	assert!(iter.next().is_none());
 }
+
+#[test]
+fn post_order_iter() {
+	let component = common::deeply_nested();
+	let order: String = component
+		.iter()
+		.with_order(IterOrder::PostOrder)
+		.filter_map(Component::shallow_text)
+		.collect();
+
+	assert_eq!(order, "cdbghfea");
+
+	let mut component = component;
+	let order: String = component
+		.iter_mut()
+		.with_order(IterOrder::PostOrder)
+		.filter_map(|node| match node.content {
+			typewheel::Content::Text(text) => Some(text.clone()),
+			_ => None,
+		})
+		.collect();
+
+	assert_eq!(order, "cdbghfea");
+}
+
+#[test]
+fn reverse_depth_first_iter() {
+	let component = common::deeply_nested();
+	let order: String = component
+		.iter()
+		.with_order(IterOrder::ReverseDepthFirst)
+		.filter_map(Component::shallow_text)
+		.collect();
+
+	assert_eq!(order, "hgfedcba");
+
+	let component = Component::translate("a", ["b", "c"]).with_extra(["d"]);
+	let order: Vec<_> = component
+		.iter()
+		.with_translate_args()
+		.with_order(IterOrder::ReverseDepthFirst)
+		.collect();
+	let mut expected: Vec<_> = component.iter().with_translate_args().collect();
+	expected.reverse();
+
+	assert_eq!(order, expected);
+}
+
+#[test]
+fn double_ended_visit() {
+	use typewheel::iter::Visit;
+
+	let component = common::deeply_nested();
+	let forward: Vec<_> = component.visit().collect();
+	let mut backward: Vec<_> = component.visit().rev().collect();
+	backward.reverse();
+
+	assert_eq!(forward, backward);
+	assert_eq!(backward.first(), Some(&Visit::Push(&component)));
+
+	// Consuming from both ends never yields an operation twice.
+	let mut iter = component.visit();
+	let mut mixed = Vec::new();
+	let mut back = Vec::new();
+	while let Some(op) = iter.next() {
+		mixed.push(op);
+		if let Some(op) = iter.next_back() {
+			back.push(op);
+		}
+	}
+	mixed.extend(back.into_iter().rev());
+
+	assert_eq!(mixed, forward);
+}
+
+#[test]
+fn styled_iter() {
+	use typewheel::path::ComponentPath;
+	use typewheel::{HoverEvent, Style, TextColor};
+
+	let component = Component::text("a").with_color(TextColor::Red).with_extra([
+		Component::text("b")
+			.with_bold(true)
+			.with_extra([Component::text("c").with_color(TextColor::Blue)]),
+		Component::text("d"),
+	]);
+
+	let nodes: Vec<_> = component
+		.iter_styled()
+		.map(|styled| {
+			(
+				styled.node.shallow_text().unwrap(),
+				styled.style.color,
+				styled.style.bold,
+				styled.depth,
+			)
+		})
+		.collect();
+
+	assert_eq!(
+		nodes,
+		[
+			("a", Some(TextColor::Red), None, 0),
+			("b", Some(TextColor::Red), Some(true), 1),
+			("c", Some(TextColor::Blue), Some(true), 2),
+			("d", Some(TextColor::Red), None, 1),
+		]
+	);
+
+	assert_eq!(
+		component.effective_style_at(&"extra[0].extra[0]".parse().unwrap()),
+		component.iter_styled().nth(2).map(|styled| styled.style)
+	);
+
+	// Translation arguments inherit the style of their translation, but hover text does not.
+	let component = Component::translate("t", [Component::text("x").with_italic(true)])
+		.with_color(TextColor::Red)
+		.with_hover_event(HoverEvent::show_text(Component::text("y").with_bold(true)));
+
+	let style = component
+		.effective_style_at(&ComponentPath::root().arg(0))
+		.unwrap();
+	assert_eq!(style.color, Some(TextColor::Red));
+	assert_eq!(style.italic, Some(true));
+
+	assert_eq!(
+		component.effective_style_at(&"hover".parse().unwrap()),
+		Some(Style::bold(true))
+	);
+	assert_eq!(
+		component.effective_style_at(&"with[1]".parse().unwrap()),
+		None
+	);
+}
+
+#[test]
+fn mutable_iter() {
+	use typewheel::Content;
+
+	let mut component = common::deeply_nested();
+	for node in component.iter_mut().with_order(IterOrder::BreadthFirst) {
+		if let Content::Text(text) = node.content {
+			*text = text.to_uppercase();
+		}
+	}
+
+	let text: String = component
+		.iter()
+		.filter_map(Component::shallow_text)
+		.collect();
+	assert_eq!(text, "ABCDEFGH");
+}
+
+#[test]
+fn rewriting_walker() {
+	use typewheel::iter::Rewrite;
+	use typewheel::HoverEvent;
+
+	let mut component = Component::translate("t", [Component::text("a").with_extra(["x"])])
+		.with_hover_event(HoverEvent::show_text("x"))
+		.with_extra([
+			Component::text("b").with_extra(["x", "c"]),
+			Component::text("x"),
+		]);
+
+	let mut plain = component.clone();
+	plain.visit_mut().apply(|node| {
+		if node.shallow_text() == Some("x") {
+			Rewrite::Remove
+		} else {
+			Rewrite::Continue
+		}
+	});
+
+	assert_eq!(
+		plain,
+		Component::translate("t", [Component::text("a").with_extra(["x"])])
+			.with_hover_event(HoverEvent::show_text("x"))
+			.with_extra([Component::text("b").with_extra(["c"])])
+	);
+
+	let mut visited = Vec::new();
+	component
+		.visit_mut()
+		.with_translate_args()
+		.with_hover_text()
+		.apply(|node| {
+			visited.push(node.shallow_text().unwrap_or("t").to_owned());
+			match node.shallow_text() {
+				Some("x") => Rewrite::Replace(Component::text("y")),
+				Some("b") => Rewrite::Skip,
+				_ => Rewrite::Continue,
+			}
+		});
+
+	assert_eq!(visited, ["t", "x", "a", "x", "b", "x"]);
+	assert_eq!(
+		component,
+		Component::translate("t", [Component::text("a").with_extra(["y"])])
+			.with_hover_event(HoverEvent::show_text("y"))
+			.with_extra([
+				Component::text("b").with_extra(["x", "c"]),
+				Component::text("y"),
+			])
+	);
+}
+
+#[test]
+fn owned_iter() {
+	let detach = |node: &Component| {
+		let mut node = node.clone();
+		node.clear_extra();
+		node
+	};
+
+	let component = common::deeply_nested();
+	for order in [
+		IterOrder::DepthFirst,
+		IterOrder::BreadthFirst,
+		IterOrder::PostOrder,
+		IterOrder::ReverseDepthFirst,
+	] {
+		let expected: Vec<_> = component.iter().with_order(order).map(detach).collect();
+		let owned: Vec<_> = component.clone().into_iter().with_order(order).collect();
+		assert_eq!(owned, expected, "{order:?}");
+	}
+}
+
+#[test]
+fn owned_visit() {
+	use typewheel::iter::{OwnedVisit, Visit};
+
+	let component = common::deeply_nested();
+	let borrowed: Vec<_> = component
+		.visit()
+		.map(|op| match op {
+			Visit::Push(node) => Some(node.shallow_text().unwrap().to_owned()),
+			Visit::Pop(_) => None,
+		})
+		.collect();
+	let owned: Vec<_> = component
+		.clone()
+		.into_visit()
+		.map(|op| match op {
+			OwnedVisit::Push(node) => {
+				assert!(node.extra.is_empty());
+				Some(node.shallow_text().unwrap().to_owned())
+			}
+			OwnedVisit::Pop => None,
+		})
+		.collect();
+
+	assert_eq!(owned, borrowed);
+
+	// Rebuilding the tree from its operations gives back the original.
+	let mut stack = vec![Component::empty()];
+	for op in component.clone().into_visit() {
+		match op {
+			OwnedVisit::Push(node) => stack.push(node),
+			OwnedVisit::Pop => {
+				let node = stack.pop().unwrap();
+				stack.last_mut().unwrap().append([node]);
+			}
+		}
+	}
+
+	assert_eq!(stack.pop().unwrap().extra, [component]);
+}
+
+#[test]
+fn nested_text_iter() {
+	use typewheel::iter::Visit;
+	use typewheel::path::PathStep;
+	use typewheel::{EntityHover, HoverEvent};
+
+	let component = Component::translate(
+		"a",
+		[
+			Component::text("b").with_hover_event(HoverEvent::show_entity(EntityHover::new(
+				Default::default(),
+				"c",
+				"minecraft:pig",
+			))),
+		],
+	)
+	.with_hover_event(HoverEvent::show_text(
+		Component::text("d").with_extra(["e"]),
+	))
+	.with_extra(["f"]);
+
+	// Nested text is skipped unless it is asked for.
+	let plain: String = component
+		.iter()
+		.filter_map(Component::shallow_text)
+		.collect();
+	assert_eq!(plain, "f");
+
+	let all: Vec<_> = component
+		.iter()
+		.with_translate_args()
+		.with_hover_text()
+		.tagged()
+		.map(|(role, node)| (role, node.shallow_text().unwrap_or("a")))
+		.collect();
+
+	assert_eq!(
+		all,
+		[
+			(None, "a"),
+			(Some(PathStep::HoverText), "d"),
+			(Some(PathStep::Extra(0)), "e"),
+			(Some(PathStep::Arg(0)), "b"),
+			(Some(PathStep::EntityName), "c"),
+			(Some(PathStep::Extra(0)), "f"),
+		]
+	);
+
+	// The visiting iterator pushes the same nodes in the same order, from either end.
+	let pushed: Vec<_> = component
+		.visit()
+		.with_translate_args()
+		.with_hover_text()
+		.tagged()
+		.filter_map(|(role, op)| match op {
+			Visit::Push(node) => Some((role, node.shallow_text().unwrap_or("a"))),
+			Visit::Pop(_) => None,
+		})
+		.collect();
+	assert_eq!(pushed, all);
+
+	let mut reversed: Vec<_> = component
+		.visit()
+		.with_translate_args()
+		.with_hover_text()
+		.rev()
+		.collect();
+	reversed.reverse();
+	let forward: Vec<_> = component
+		.visit()
+		.with_translate_args()
+		.with_hover_text()
+		.collect();
+	assert_eq!(reversed, forward);
+	assert_eq!(forward.len(), 12);
+}
@@ -0,0 +1,155 @@
+#![cfg(feature = "rayon")]
+
+use rayon::prelude::*;
+use typewheel::codec::PlainTextCodec;
+use typewheel::par;
+use typewheel::visitor::{self, ComponentFolder};
+use typewheel::{Component, Content, HoverEvent, TextColor};
+
+mod common;
+
+/// Builds a wide tree with `width` children per node, `depth` levels deep.
+fn wide_tree(width: usize, depth: usize) -> Component {
+	let mut component = Component::text(depth.to_string());
+	if depth > 0 {
+		component.append((0..width).map(|_| wide_tree(width, depth - 1)));
+	}
+
+	component
+}
+
+#[test]
+fn par_iter() {
+	let component = wide_tree(6, 4);
+	let mut expected: Vec<_> = component.iter().collect();
+	let mut nodes: Vec<_> = component.par_iter().collect();
+
+	expected.sort_by_key(|node| node as *const _);
+	nodes.sort_by_key(|node| node as *const _);
+	assert_eq!(nodes.len(), component.tree_size());
+	assert!(expected
+		.iter()
+		.zip(&nodes)
+		.all(|(a, b)| std::ptr::eq(*a, *b)));
+
+	let component = Component::translate("a", ["b"])
+		.with_hover_event(HoverEvent::show_text("c"))
+		.with_extra(["d"]);
+	assert_eq!(component.par_iter().count(), 2);
+	assert_eq!(component.par_iter().with_translate_args().count(), 3);
+	assert_eq!(
+		component
+			.par_iter()
+			.with_translate_args()
+			.with_hover_text()
+			.count(),
+		4
+	);
+}
+
+#[test]
+fn par_iter_mut() {
+	let mut component = common::deeply_nested();
+	component
+		.par_iter_mut()
+		.for_each(|node| node.style.color = Some(TextColor::Red));
+	assert!(component
+		.iter()
+		.all(|node| node.style.color == Some(TextColor::Red)));
+
+	let mut component = wide_tree(5, 4);
+	let count = (&mut component)
+		.into_par_iter()
+		.map(|node| {
+			node.style.bold = Some(true);
+		})
+		.count();
+	assert_eq!(count, component.tree_size());
+	assert!(component.iter().all(|node| node.style.bold == Some(true)));
+}
+
+#[derive(Clone)]
+struct Suffix(&'static str);
+
+impl ComponentFolder for Suffix {
+	fn fold_text(&mut self, text: String) -> Content {
+		Content::Text(text + self.0)
+	}
+}
+
+#[test]
+fn folding() {
+	let component = wide_tree(4, 3).with_extra([Component::translate("a", ["b"])]);
+	let expected = Suffix("!").fold_component(component.clone());
+
+	assert_eq!(
+		par::fold_component(&Suffix("!"), component.clone()),
+		expected
+	);
+	assert_eq!(
+		par::fold_all(&Suffix("!"), vec![component.clone(), Component::text("c")]),
+		[expected, Component::text("c!")]
+	);
+}
+
+/// A folder that leaves subtrees with insertion text untouched.
+#[derive(Clone)]
+struct SkipInserted;
+
+impl ComponentFolder for SkipInserted {
+	fn fold_component(&mut self, component: Component) -> Component {
+		if component.style.insertion.is_some() {
+			return component;
+		}
+
+		visitor::fold_component(self, component)
+	}
+
+	fn fold_text(&mut self, text: String) -> Content {
+		Content::Text(text + "!")
+	}
+}
+
+#[test]
+fn folding_overridden_recursion() {
+	let component = wide_tree(3, 3).with_extra([
+		Component::text("a")
+			.with_insertion("x")
+			.with_extra([wide_tree(2, 2)]),
+		Component::text("b").with_extra([Component::text("c").with_insertion("y")]),
+	]);
+
+	let expected = SkipInserted.fold_component(component.clone());
+	let folded = par::fold_component(&SkipInserted, component.clone());
+
+	assert_eq!(folded, expected);
+	assert_eq!(folded.extra[3], component.extra[3]);
+	assert_eq!(folded.extra[4].extra[0], component.extra[4].extra[0]);
+	assert_eq!(folded.extra[4].shallow_text(), Some("b!"));
+}
+
+#[test]
+fn batch_encoding() {
+	let components: Vec<_> = (0..100)
+		.map(|i| Component::text(i.to_string()).with_extra(["!"]))
+		.collect();
+
+	let encoded = par::serialize_all(PlainTextCodec, &components);
+	assert_eq!(encoded.len(), 100);
+	assert_eq!(encoded[42], "42!");
+
+	#[cfg(feature = "json")]
+	{
+		use typewheel::codec::JsonCodec;
+
+		let encoded: Vec<String> = par::serialize_all(JsonCodec, &components)
+			.into_iter()
+			.collect::<Result<_, _>>()
+			.unwrap();
+		let decoded: Vec<Component> = par::deserialize_all(JsonCodec, encoded)
+			.into_iter()
+			.collect::<Result<_, _>>()
+			.unwrap();
+		assert_eq!(decoded, components);
+	}
+}
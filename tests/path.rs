@@ -0,0 +1,132 @@
+use typewheel::path::{ComponentPath, PathStep};
+use typewheel::{Component, EntityHover, HoverEvent, TextColor};
+
+mod common;
+
+#[test]
+fn parsing() {
+	let path: ComponentPath = "extra[2].with[0].hover.name.extra[1].hover"
+		.parse()
+		.unwrap();
+	assert_eq!(
+		path.steps(),
+		[
+			PathStep::Extra(2),
+			PathStep::Arg(0),
+			PathStep::EntityName,
+			PathStep::Extra(1),
+			PathStep::HoverText,
+		]
+	);
+	assert_eq!(
+		path.to_string(),
+		"extra[2].with[0].hover.name.extra[1].hover"
+	);
+
+	let root: ComponentPath = "".parse().unwrap();
+	assert!(root.is_root());
+	assert_eq!(root.to_string(), "");
+
+	let path: ComponentPath = "extra[0].with[10]".parse().unwrap();
+	assert_eq!(path.steps(), [PathStep::Extra(0), PathStep::Arg(10)]);
+
+	for (invalid, position) in [
+		("extra", 0),
+		("extra[x]", 0),
+		("extra[]", 0),
+		("extra[+1]", 0),
+		("extra[01]", 0),
+		("extra[0].with[-0]", 9),
+		("extra[1].", 9),
+		("extra[1]with[0]", 8),
+		("extra[1].children[0]", 9),
+	] {
+		let error = invalid.parse::<ComponentPath>().unwrap_err();
+		assert_eq!(error.position, position, "{invalid}");
+	}
+}
+
+#[test]
+fn lookups() {
+	let mut component = common::deeply_nested();
+	let path = ComponentPath::root().child(1).child(0).child(1);
+
+	assert_eq!(component.get(&path), Some(&Component::text("h")));
+	assert_eq!(component.get(&ComponentPath::root().child(2)), None);
+	assert_eq!(component.get(&ComponentPath::root().arg(0)), None);
+
+	component.get_mut(&path).unwrap().style.color = Some(TextColor::Red);
+	assert_eq!(
+		component.get(&path),
+		Some(&Component::text("h").with_color(TextColor::Red))
+	);
+
+	let old = component.replace(&path.parent().unwrap(), Component::text("i"));
+	assert_eq!(old.unwrap().extra.len(), 2);
+	assert_eq!(component.get(&path), None);
+	assert_eq!(component.replace(&path, Component::text("j")), None);
+
+	let hovered = Component::text("a").with_hover_event(HoverEvent::show_entity(EntityHover::new(
+		Default::default(),
+		Component::translate("entity.minecraft.pig", [Component::text("b")]),
+		"minecraft:pig",
+	)));
+	let path: ComponentPath = "hover.name.with[0]".parse().unwrap();
+	assert_eq!(hovered.get(&path), Some(&Component::text("b")));
+}
+
+#[test]
+fn cursors() {
+	let component = Component::translate("a", [Component::text("b"), Component::text("c")])
+		.with_extra([Component::text("d").with_extra(["e"]), Component::text("f")]);
+
+	let mut cursor = component.cursor();
+	assert!(!cursor.parent());
+	assert!(!cursor.next_sibling());
+
+	assert!(cursor.first_child());
+	assert!(cursor.first_child());
+	assert_eq!(cursor.node(), &Component::text("e"));
+	assert_eq!(cursor.path().to_string(), "extra[0].extra[0]");
+	assert!(!cursor.next_sibling());
+
+	assert!(cursor.parent());
+	assert!(cursor.next_sibling());
+	assert_eq!(cursor.node(), &Component::text("f"));
+	assert!(!cursor.next_sibling());
+	assert!(cursor.prev_sibling());
+	assert_eq!(cursor.path().to_string(), "extra[0]");
+
+	assert!(cursor.parent());
+	assert!(cursor.into_translation_arg(0));
+	assert!(cursor.next_sibling());
+	assert_eq!(cursor.node(), &Component::text("c"));
+	assert_eq!(cursor.path().to_string(), "with[1]");
+	assert!(!cursor.into_translation_arg(0));
+
+	assert_eq!(component.get(cursor.path()), Some(cursor.node()));
+}
+
+#[test]
+fn path_iter() {
+	let component = common::deeply_nested();
+	for (path, node) in component.iter_paths() {
+		assert_eq!(component.get(&path), Some(node));
+	}
+
+	let nodes: Vec<_> = component.iter_paths().map(|(_, node)| node).collect();
+	let expected: Vec<_> = component.iter().collect();
+	assert_eq!(nodes, expected);
+
+	let component = Component::translate("a", [Component::text("b")])
+		.with_hover_event(HoverEvent::show_text("c"))
+		.with_extra(["d"]);
+	let paths: Vec<_> = component
+		.iter_paths()
+		.with_translate_args()
+		.with_hover_text()
+		.map(|(path, _)| path.to_string())
+		.collect();
+
+	assert_eq!(paths, ["", "hover", "with[0]", "extra[0]"]);
+}
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use typewheel::placeholder::{Placeholder, PlaceholderReplacer, UnknownPlaceholder};
+use typewheel::{Component, HoverEvent, Style, TextColor};
+
+fn replacer() -> PlaceholderReplacer<HashMap<&'static str, Placeholder>> {
+	PlaceholderReplacer::new(HashMap::from([
+		("player", Component::text("Notch").with_bold(true).into()),
+		("count", "3".into()),
+		("red", Style::color(TextColor::Red).into()),
+	]))
+}
+
+#[test]
+fn split_across_siblings() {
+	let component = Component::text("Hello <pla")
+		.with_color(TextColor::Gray)
+		.with_extra([Component::text("yer>!").with_italic(true)]);
+
+	assert_eq!(
+		replacer().replace(component),
+		Component::text("Hello ")
+			.with_color(TextColor::Gray)
+			.with_extra([
+				Component::text("Notch").with_bold(true),
+				Component::text("!").with_italic(true),
+			])
+	);
+}
+
+#[test]
+fn covered_nodes_are_kept() {
+	// Nodes that a placeholder covers entirely stay in the tree as empty nodes.
+	let component = Component::text("<pl").with_extra([
+		Component::text("ay").with_bold(true),
+		Component::text("er>!"),
+	]);
+
+	assert_eq!(
+		replacer().replace(component),
+		Component::empty().with_extra([
+			Component::text("Notch").with_bold(true),
+			Component::empty().with_bold(true),
+			Component::text("!"),
+		])
+	);
+}
+
+#[test]
+fn style_values() {
+	let component = Component::text("a <red>b").with_extra(["c"]);
+
+	assert_eq!(
+		replacer().replace(component),
+		Component::text("a ").with_extra([Component::empty()
+			.with_color(TextColor::Red)
+			.with_extra(["b", "c"])])
+	);
+}
+
+#[test]
+fn nested_flows() {
+	let component = Component::translate("chat.type.text", ["<player>", "<count>"])
+		.with_hover_event(HoverEvent::show_text("<count> friends"));
+
+	assert_eq!(
+		replacer().replace(component),
+		Component::translate(
+			"chat.type.text",
+			[
+				Component::empty().with_extra([Component::text("Notch").with_bold(true)]),
+				Component::text("3"),
+			]
+		)
+		.with_hover_event(HoverEvent::show_text("3 friends"))
+	);
+}
+
+#[test]
+fn unknown_policy() {
+	let component = Component::text("<missing> <count>");
+
+	assert_eq!(
+		replacer().replace(component.clone()),
+		Component::text("<missing> 3")
+	);
+	assert_eq!(
+		replacer()
+			.with_unknown(UnknownPlaceholder::Remove)
+			.replace(component.clone()),
+		Component::text(" 3")
+	);
+	assert_eq!(
+		replacer()
+			.with_unknown(UnknownPlaceholder::replace("?"))
+			.replace(component),
+		Component::text("? 3")
+	);
+}
@@ -0,0 +1,152 @@
+use typewheel::query::Query;
+use typewheel::{ClickEvent, Component, HoverEvent, TextColor};
+
+fn sample() -> Component {
+	Component::text("Welcome, ")
+		.with_color(TextColor::Gold)
+		.with_extra([
+			Component::text("Notch")
+				.with_bold(true)
+				.with_click_event(ClickEvent::suggest_command("/msg Notch "))
+				.with_hover_event(HoverEvent::show_text(
+					Component::text("Send a message").with_italic(true),
+				)),
+			Component::translate(
+				"chat.type.text",
+				[
+					Component::text("a").with_color(TextColor::Red),
+					Component::keybind("key.jump"),
+				],
+			)
+			.with_bold(true),
+			Component::text("!").with_color(TextColor::Hex(0x123456)),
+		])
+}
+
+fn paths(component: &Component, query: &str) -> Vec<String> {
+	let query: Query = query.parse().unwrap();
+	component
+		.query(&query)
+		.map(|(path, _)| path.to_string())
+		.collect()
+}
+
+#[test]
+fn parsing() {
+	for valid in [
+		"*",
+		"text",
+		"[bold]",
+		"text[bold=false][color=red]",
+		r#"translate[key^="chat."] > with"#,
+		"translate   with , hover text",
+		r#"text[text="a \"quoted\" \\ value"]"#,
+		"[color=#ff00aa]",
+		"text[bold] ",
+		"  text , hover  ",
+	] {
+		assert!(valid.parse::<Query>().is_ok(), "{valid}");
+	}
+
+	for (invalid, position) in [
+		("", 0),
+		("paragraph", 0),
+		("text[", 5),
+		("text[size=1]", 5),
+		("text[bold=maybe]", 5),
+		("text[color^=red]", 5),
+		("text[color=reddish]", 5),
+		("text >", 6),
+		("text,", 5),
+		(r#"text[text="a]"#, 13),
+		("text[bold]x", 10),
+	] {
+		let error = invalid.parse::<Query>().unwrap_err();
+		assert_eq!(error.position, position, "{invalid}");
+	}
+}
+
+#[test]
+fn content_and_style() {
+	let component = sample();
+
+	assert_eq!(
+		paths(&component, "text[bold]"),
+		["extra[0]", "extra[1].with[0]"]
+	);
+	// Hover text does not inherit the style of the node it belongs to.
+	assert_eq!(paths(&component, "text[color=gold]"), ["", "extra[0]"]);
+	assert_eq!(paths(&component, "[color=#123456]"), ["extra[2]"]);
+	assert_eq!(
+		paths(&component, "[bold][color!=gold]"),
+		["extra[1].with[0]"]
+	);
+	assert_eq!(paths(&component, "keybind[bold]"), ["extra[1].with[1]"]);
+	assert_eq!(paths(&component, "[italic]"), ["extra[0].hover"]);
+	assert_eq!(paths(&component, r#"[key$=".jump"]"#), ["extra[1].with[1]"]);
+	assert_eq!(paths(&component, "[text*=otc]"), ["extra[0]"]);
+	assert_eq!(paths(&component, "[click=suggest_command]"), ["extra[0]"]);
+	assert_eq!(paths(&component, "[hover]"), ["extra[0]"]);
+	assert_eq!(
+		paths(&component, "text[text!=Notch][bold=false]"),
+		["", "extra[0].hover", "extra[2]"]
+	);
+}
+
+#[test]
+fn inherited_attributes() {
+	let component = Component::text("a")
+		.with_font("minecraft:uniform")
+		.with_click_event(ClickEvent::run_command("/spawn"))
+		.with_extra([
+			Component::text("b").with_extra(["c"]),
+			Component::text("d").with_font("minecraft:alt"),
+		]);
+
+	assert_eq!(
+		paths(&component, "[font=minecraft:uniform]"),
+		["", "extra[0]", "extra[0].extra[0]"]
+	);
+	assert_eq!(paths(&component, r#"[font$=":alt"]"#), ["extra[1]"]);
+	assert_eq!(
+		paths(&component, "text[text=c][click=run_command]"),
+		["extra[0].extra[0]"]
+	);
+	assert_eq!(paths(&component, "[click]").len(), 4);
+	assert_eq!(paths(&component, "[insertion]"), Vec::<String>::new());
+}
+
+#[test]
+fn combinators() {
+	let component = sample();
+
+	assert_eq!(
+		paths(&component, r#"translate[key^="chat."] > with"#),
+		["extra[1].with[0]", "extra[1].with[1]"]
+	);
+	assert_eq!(paths(&component, "hover"), ["extra[0].hover"]);
+	assert_eq!(paths(&component, "text > text > text"), ["extra[0].hover"]);
+	assert_eq!(paths(&component, "text > keybind"), Vec::<String>::new());
+	assert_eq!(paths(&component, "text keybind"), ["extra[1].with[1]"]);
+	assert_eq!(
+		paths(&component, "hover, keybind"),
+		["extra[0].hover", "extra[1].with[1]"]
+	);
+
+	let query: Query = "translate > with".parse().unwrap();
+	let args: Vec<_> = component.query(&query).map(|(_, node)| node).collect();
+	assert_eq!(
+		args,
+		[
+			&Component::text("a").with_color(TextColor::Red),
+			&Component::keybind("key.jump"),
+		]
+	);
+
+	// A cloned iterator resumes from the same position independently.
+	let mut matches = component.query(&query);
+	matches.next();
+	let rest: Vec<_> = matches.clone().map(|(path, _)| path.to_string()).collect();
+	assert_eq!(rest, ["extra[1].with[1]"]);
+	assert_eq!(matches.count(), 1);
+}
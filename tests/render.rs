@@ -0,0 +1,151 @@
+use typewheel::render::{ComponentRenderer, RenderPipeline};
+use typewheel::{Component, HoverEvent, Style, TextColor};
+
+mod common;
+
+struct Viewer {
+	name: &'static str,
+}
+
+/// Replaces every `@s` score holder with the viewer's name.
+struct Selectors;
+
+impl ComponentRenderer<Viewer> for Selectors {
+	fn render_score(
+		&self,
+		name: &str,
+		objective: &str,
+		value: &str,
+		style: &Style,
+		viewer: &Viewer,
+	) -> Component {
+		let name = if name == "@s" { viewer.name } else { name };
+		let mut out = Component::score(name, objective, value);
+		out.style = self.render_style(style, viewer);
+		out
+	}
+}
+
+/// Upper-cases all text.
+struct Shout;
+
+impl ComponentRenderer<Viewer> for Shout {
+	fn render_text(&self, text: &str, style: &Style, viewer: &Viewer) -> Component {
+		let mut out = Component::text(text.to_uppercase());
+		out.style = self.render_style(style, viewer);
+		out
+	}
+}
+
+#[test]
+fn default_render_is_identity() {
+	struct Identity;
+	impl ComponentRenderer<()> for Identity {}
+
+	let component = common::styled_hello();
+	assert_eq!(Identity.render(&component, &()), component);
+}
+
+#[test]
+fn renders_nested_content() {
+	let component = Component::translate(
+		"chat.type.text",
+		[Component::score("@s", "kills", "3"), Component::text("hi")],
+	)
+	.with_hover_event(HoverEvent::show_text(Component::score("@s", "deaths", "1")))
+	.with_extra([Component::score("@s", "level", "10").with_color(TextColor::Gold)]);
+
+	let viewer = Viewer { name: "Notch" };
+	let rendered = Selectors.render(&component, &viewer);
+
+	assert_eq!(
+		rendered,
+		Component::translate(
+			"chat.type.text",
+			[
+				Component::score("Notch", "kills", "3"),
+				Component::text("hi")
+			],
+		)
+		.with_hover_event(HoverEvent::show_text(Component::score(
+			"Notch", "deaths", "1"
+		)))
+		.with_extra([Component::score("Notch", "level", "10").with_color(TextColor::Gold)])
+	);
+}
+
+#[test]
+fn pipeline_runs_in_order() {
+	let pipeline = RenderPipeline::new().then(Selectors).then(Shout);
+	assert_eq!(pipeline.len(), 2);
+
+	let component = Component::text("hello ").with_extra([Component::score("@s", "kills", "3")]);
+	let rendered = pipeline.render(&component, &Viewer { name: "Notch" });
+
+	assert_eq!(
+		rendered,
+		Component::text("HELLO ").with_extra([Component::score("Notch", "kills", "3")])
+	);
+
+	let empty = RenderPipeline::<Viewer>::new();
+	assert_eq!(empty.render(&component, &Viewer { name: "" }), component);
+}
+
+#[test]
+fn virtual_content() {
+	use typewheel::render::VirtualRenderer;
+
+	let ping = Component::deferred_or(
+		|viewer: &Viewer| Component::text(format!("{}: 20ms", viewer.name)),
+		"unknown",
+	)
+	.with_color(TextColor::Green)
+	.with_extra([" (avg)"]);
+
+	let rendered = VirtualRenderer.render(&ping, &Viewer { name: "Notch" });
+	assert_eq!(
+		rendered,
+		Component::text("Notch: 20ms")
+			.with_color(TextColor::Green)
+			.with_extra([" (avg)"])
+	);
+
+	// Rendering for a different context or serializing directly uses the fallback.
+	let fallback = Component::empty()
+		.with_color(TextColor::Green)
+		.with_extra(["unknown", " (avg)"]);
+	assert_eq!(VirtualRenderer.render(&ping, &()), fallback);
+
+	#[cfg(feature = "json")]
+	{
+		use typewheel::codec::{ComponentCodec, JsonCodec};
+
+		assert_eq!(
+			JsonCodec.serialize(&ping).unwrap(),
+			JsonCodec.serialize(&fallback).unwrap()
+		);
+	}
+
+	// Clones share the same renderer.
+	assert_eq!(ping.clone(), ping);
+
+	// Stages before the virtual renderer keep the virtual content for it to resolve.
+	let pipeline = RenderPipeline::new().then(Shout).then(VirtualRenderer);
+	assert_eq!(
+		pipeline.render(&ping, &Viewer { name: "Notch" }),
+		Component::text("Notch: 20ms")
+			.with_color(TextColor::Green)
+			.with_extra([" (AVG)"])
+	);
+
+	let unresolved = Shout.render(&ping, &Viewer { name: "Notch" });
+	assert_eq!(unresolved.content, ping.content);
+	assert_eq!(unresolved.extra, [Component::text(" (AVG)")]);
+
+	// Displaying virtual content shows its whole fallback.
+	let ping = Component::deferred_or(
+		|_: &Viewer| Component::text("20ms"),
+		Component::text("unknown").with_extra([" player"]),
+	);
+	assert_eq!(ping.content.to_string(), "unknown player");
+}
@@ -0,0 +1,128 @@
+use typewheel::iter::{IterOrder, Visit};
+use typewheel::shared::SharedComponent;
+use typewheel::{Component, Content, HoverEvent, TextColor};
+
+mod common;
+
+#[test]
+fn conversions() {
+	let shared = SharedComponent::from(common::styled_hello());
+	assert_eq!(shared.to_component(), common::styled_hello());
+
+	let copy = shared.clone();
+	assert!(SharedComponent::ptr_eq(&shared, &copy));
+	assert_eq!(Component::from(copy), common::styled_hello());
+	assert_eq!(Component::from(shared), common::styled_hello());
+}
+
+#[test]
+fn path_copying() {
+	let original = SharedComponent::from(common::deeply_nested());
+	let mut edited = original.clone();
+
+	*edited.get_mut(&[1, 0, 1]).unwrap() = Component::text("i").with_color(TextColor::Red).into();
+
+	assert_eq!(original.to_component(), common::deeply_nested());
+	assert_eq!(
+		edited.get(&[1, 0, 1]).unwrap().to_component(),
+		Component::text("i").with_color(TextColor::Red)
+	);
+
+	// Only the nodes on the path were copied.
+	assert!(!SharedComponent::ptr_eq(&original, &edited));
+	assert!(!SharedComponent::ptr_eq(
+		original.get(&[1, 0]).unwrap(),
+		edited.get(&[1, 0]).unwrap()
+	));
+	assert!(SharedComponent::ptr_eq(
+		original.get(&[0]).unwrap(),
+		edited.get(&[0]).unwrap()
+	));
+	assert!(SharedComponent::ptr_eq(
+		original.get(&[1, 0, 0]).unwrap(),
+		edited.get(&[1, 0, 0]).unwrap()
+	));
+
+	assert!(edited.get_mut(&[5]).is_none());
+}
+
+#[test]
+fn nested_sharing() {
+	let component = Component::text("a")
+		.with_hover_event(HoverEvent::show_text(common::styled_hello()))
+		.with_extra([Component::translate("b", [common::deeply_nested()])]);
+
+	let original = SharedComponent::from(component.clone());
+	let mut edited = original.clone();
+	edited.style_mut().color = Some(TextColor::Red);
+	edited.get_mut(&[0]).unwrap().style_mut().bold = Some(true);
+
+	// Hover text and translation arguments are not copied along with the nodes they belong to.
+	assert!(!SharedComponent::ptr_eq(&original, &edited));
+	assert!(SharedComponent::ptr_eq(
+		original.hover_text().unwrap(),
+		edited.hover_text().unwrap()
+	));
+	assert!(SharedComponent::ptr_eq(
+		&original.extra()[0].args()[0],
+		&edited.extra()[0].args()[0]
+	));
+
+	assert_eq!(Component::from(original), component);
+	assert_eq!(
+		edited.to_component(),
+		Component::text("a")
+			.with_color(TextColor::Red)
+			.with_hover_event(HoverEvent::show_text(common::styled_hello()))
+			.with_extra([Component::translate("b", [common::deeply_nested()]).with_bold(true)])
+	);
+
+	// Nested components written through mutable references replace the stored ones.
+	edited.style_mut().hover_event = Some(HoverEvent::show_text("c"));
+	assert_eq!(
+		edited.hover_text().unwrap().to_component(),
+		Component::text("c")
+	);
+
+	let translation = edited.get_mut(&[0]).unwrap();
+	if let Content::Translation { key, .. } = &mut *translation.content_mut() {
+		key.push('!');
+	}
+	assert_eq!(translation.args().len(), 1);
+
+	*translation.content_mut() = Content::Text("d".into());
+	assert!(translation.args().is_empty());
+	assert_eq!(
+		translation.to_component(),
+		Component::text("d").with_bold(true)
+	);
+}
+
+#[test]
+fn shared_iterators() {
+	let shared = SharedComponent::from(common::deeply_nested());
+	let text = |node: &SharedComponent| node.to_component().shallow_text().unwrap().to_owned();
+
+	let depth_first: String = shared.iter().map(text).collect();
+	assert_eq!(depth_first, "abcdefgh");
+
+	let breadth_first: String = shared
+		.iter()
+		.with_order(IterOrder::BreadthFirst)
+		.map(text)
+		.collect();
+	assert_eq!(breadth_first, "abecdfgh");
+
+	let mut depth = 0;
+	let mut max_depth = 0;
+	for op in shared.visit() {
+		match op {
+			Visit::Push(_) => depth += 1,
+			Visit::Pop(_) => depth -= 1,
+		}
+		max_depth = max_depth.max(depth);
+	}
+
+	assert_eq!(depth, 0);
+	assert_eq!(max_depth, 4);
+}
@@ -0,0 +1,138 @@
+use typewheel::iter::IterOrder;
+use typewheel::{ClickEvent, Component, HoverEvent, TextColor};
+
+mod common;
+
+#[test]
+fn sizes() {
+	let component = common::deeply_nested();
+	assert_eq!(component.tree_size(), 8);
+	assert_eq!(component.content_size(), 8);
+	assert_eq!(component.depth(), 3);
+
+	let component = Component::translate(
+		"a",
+		[
+			Component::text("b").with_extra(["c"]),
+			Component::translate("d", ["e"]),
+		],
+	)
+	.with_extra(["f"]);
+	assert_eq!(component.tree_size(), 2);
+	assert_eq!(component.content_size(), 6);
+	assert_eq!(component.depth(), 1);
+}
+
+#[test]
+fn exact_size_hints() {
+	let component = common::deeply_nested();
+
+	for order in [
+		IterOrder::DepthFirst,
+		IterOrder::BreadthFirst,
+		IterOrder::PostOrder,
+		IterOrder::ReverseDepthFirst,
+	] {
+		let mut iter = component.iter().with_order(order);
+		for remaining in (0..=8).rev() {
+			assert_eq!(iter.len(), remaining, "{order:?}");
+			iter.next();
+		}
+	}
+
+	let mut iter = component.visit();
+	assert_eq!(iter.len(), 16);
+	iter.next();
+	iter.next_back();
+	assert_eq!(iter.len(), 14);
+	assert_eq!(iter.count(), 14);
+
+	let component = Component::translate("a", ["b"])
+		.with_hover_event(HoverEvent::show_text("c"))
+		.with_extra(["d"]);
+	let iter = component.iter().with_translate_args().with_hover_text();
+	assert_eq!(iter.len(), 4);
+	assert_eq!(component.iter().len(), 2);
+
+	let mut component = common::deeply_nested();
+	assert_eq!(component.iter_styled().len(), 8);
+	assert_eq!(component.iter_paths().len(), 8);
+	assert_eq!(component.iter_mut().len(), 8);
+	assert_eq!(component.clone().into_visit().len(), 16);
+
+	let mut iter = component.into_iter();
+	iter.next();
+	assert_eq!(iter.len(), 7);
+}
+
+#[test]
+fn deep_trees() {
+	let mut component = Component::text("0");
+	for i in 1..50_000 {
+		component = Component::text(i.to_string()).with_extra([component]);
+	}
+
+	assert_eq!(component.tree_size(), 50_000);
+	assert_eq!(component.depth(), 49_999);
+	assert_eq!(component.iter().len(), 50_000);
+	assert_eq!(component.visit().len(), 100_000);
+
+	// Dropping the chain recursively would overflow the stack, so it is taken apart first.
+	let mut extra = component.take_extra();
+	while let Some(mut child) = extra.pop() {
+		extra = child.take_extra();
+	}
+}
+
+#[test]
+fn iterators_are_sync() {
+	fn assert_sync<T: Send + Sync>(_: T) {}
+
+	let mut component = common::deeply_nested();
+	assert_sync(component.iter());
+	assert_sync(component.visit());
+	assert_sync(component.iter_styled());
+	assert_sync(component.iter_paths());
+	assert_sync(component.iter_mut());
+	assert_sync(component.clone().into_iter());
+	assert_sync(component.into_visit());
+}
+
+#[test]
+fn stats() {
+	let component = Component::text("Hello, ")
+		.with_color(TextColor::Gold)
+		.with_bold(true)
+		.with_extra([
+			Component::text("world")
+				.with_bold(false)
+				.with_click_event(ClickEvent::run_command("/spawn"))
+				.with_hover_event(HoverEvent::show_text(
+					Component::text("Teleport").with_italic(true),
+				)),
+			Component::translate("chat.type.text", ["a", "b"]),
+		]);
+
+	let stats = component.stats();
+	assert_eq!(stats.nodes, 6);
+	assert_eq!(stats.depth, 1);
+	assert_eq!(stats.text_length, 22);
+	assert_eq!(stats.style.bold, 2);
+	assert_eq!(stats.style.italic, 1);
+	assert_eq!(stats.style.color, 1);
+	assert_eq!(stats.style.click_event, 1);
+	assert_eq!(stats.style.hover_event, 1);
+	assert_eq!(stats.events.run_command, 1);
+	assert_eq!(stats.events.show_text, 1);
+	assert_eq!(stats.events.copy, 0);
+
+	#[cfg(feature = "json")]
+	{
+		let actual = serde_json::to_string(&component).unwrap().len();
+		let estimate = stats.estimated_json_size;
+		assert!(
+			estimate * 4 >= actual * 3 && estimate * 4 <= actual * 5,
+			"estimated {estimate} bytes, actual {actual}"
+		);
+	}
+}
@@ -0,0 +1,73 @@
+use typewheel::template::Template;
+use typewheel::{Component, HoverEvent, TextColor};
+
+fn template() -> Template {
+	Template::compile(
+		Component::text("<player> has ")
+			.with_color(TextColor::Gray)
+			.with_hover_event(HoverEvent::show_text("Click to message <player>"))
+			.with_extra([
+				Component::translate("item.minecraft.<item>", ["<count>"]),
+				Component::text("!").with_bold(true),
+			]),
+	)
+}
+
+#[test]
+fn slots() {
+	let template = template();
+
+	assert_eq!(template.slots(), ["player", "count"]);
+	assert_eq!(template.slot("count"), Some(1));
+	assert_eq!(template.slot("item"), None);
+}
+
+#[test]
+fn render() {
+	let rendered = template().render(&[Component::text("Notch").with_bold(true), "3".into()]);
+
+	assert_eq!(
+		rendered,
+		Component::empty()
+			.with_color(TextColor::Gray)
+			.with_hover_event(HoverEvent::show_text(
+				Component::text("Click to message ")
+					.with_extra([Component::text("Notch").with_bold(true)])
+			))
+			.with_extra([
+				Component::text("Notch").with_bold(true),
+				Component::text(" has "),
+				Component::translate(
+					"item.minecraft.<item>",
+					[Component::empty().with_extra(["3"])]
+				),
+				Component::text("!").with_bold(true),
+			])
+	);
+
+	// Missing arguments are left empty.
+	let rendered = Template::parse("a<b>c").render(&[]);
+	assert_eq!(
+		rendered,
+		Component::text("a").with_extra([Component::empty(), "c".into()])
+	);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json_fragments() {
+	use typewheel::codec::{ComponentCodec, JsonCodec};
+
+	let template = template();
+
+	for args in [
+		vec![],
+		vec![Component::text("Notch")],
+		vec![Component::text("\"quoted\"").with_bold(true), "12".into()],
+	] {
+		assert_eq!(
+			template.to_json(&args).unwrap(),
+			JsonCodec.serialize(&template.render(&args)).unwrap()
+		);
+	}
+}
@@ -0,0 +1,310 @@
+use std::ops::Bound;
+use typewheel::text::TextUnit;
+use typewheel::{ClickEvent, Component, Content, TextColor};
+
+fn sample() -> Component {
+	Component::text("Hello, ")
+		.with_color(TextColor::Gold)
+		.with_extra([
+			Component::text("world")
+				.with_bold(true)
+				.with_click_event(ClickEvent::suggest_command("/hi")),
+			Component::keybind("key.jump"),
+			Component::text("!"),
+		])
+}
+
+#[test]
+fn slicing() {
+	let component = sample();
+
+	assert_eq!(
+		component.slice(3..9),
+		Component::text("lo, ")
+			.with_color(TextColor::Gold)
+			.with_extra([Component::text("wo")
+				.with_bold(true)
+				.with_click_event(ClickEvent::suggest_command("/hi"))])
+	);
+
+	// Slicing inside a child keeps its parent's style on an empty node.
+	assert_eq!(
+		component.slice(8..10),
+		Component::empty()
+			.with_color(TextColor::Gold)
+			.with_extra([Component::text("or")
+				.with_bold(true)
+				.with_click_event(ClickEvent::suggest_command("/hi"))])
+	);
+
+	assert_eq!(component.slice(..), component);
+	assert_eq!(component.slice(..=usize::MAX), component);
+	assert_eq!(
+		component.slice((Bound::Excluded(usize::MAX), Bound::Unbounded)),
+		Component::empty()
+	);
+	assert_eq!(component.slice(100..), Component::empty());
+	assert_eq!(
+		component.slice(12..),
+		Component::empty()
+			.with_color(TextColor::Gold)
+			.with_extra([Component::keybind("key.jump"), Component::text("!")])
+	);
+}
+
+#[test]
+fn splitting() {
+	let component = sample();
+	let (left, right) = component.split_at(12);
+
+	assert_eq!(
+		left,
+		Component::text("Hello, ")
+			.with_color(TextColor::Gold)
+			.with_extra([Component::text("world")
+				.with_bold(true)
+				.with_click_event(ClickEvent::suggest_command("/hi"))])
+	);
+	assert_eq!(right, component.slice(12..));
+
+	let (left, right) = component.split_at(0);
+	assert_eq!(left, Component::empty());
+	assert_eq!(right, component);
+}
+
+#[test]
+fn units() {
+	let component = Component::text("a😀").with_extra(["b"]);
+
+	assert_eq!(
+		component.slice_by(2..3, TextUnit::Chars),
+		Component::empty().with_extra(["b"])
+	);
+	assert_eq!(
+		component.slice_by(3..4, TextUnit::Utf16),
+		Component::empty().with_extra(["b"])
+	);
+
+	// Offsets in the middle of a surrogate pair round down.
+	assert_eq!(
+		component.slice_by(2.., TextUnit::Utf16),
+		component.slice_by(1.., TextUnit::Utf16)
+	);
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn graphemes() {
+	let component = Component::text("e\u{301}").with_extra(["x"]);
+
+	assert_eq!(
+		component.slice_by(1.., TextUnit::Graphemes),
+		Component::empty().with_extra(["x"])
+	);
+	assert_eq!(
+		component.split_at_by(1, TextUnit::Graphemes).0,
+		Component::text("e\u{301}")
+	);
+}
+
+#[test]
+fn replacing_across_nodes() {
+	use typewheel::text::TextReplacer;
+
+	let replacer = TextReplacer::literal("hello", |found| {
+		assert_eq!(found.style().color, Some(TextColor::Gold));
+		Component::text("bye")
+	});
+
+	let component = Component::text("say he")
+		.with_color(TextColor::Gold)
+		.with_extra([
+			Component::text("l").with_bold(true),
+			Component::text("lo there").with_italic(true),
+		]);
+
+	// Nodes whose text was covered entirely are kept, unless removing them is opted into.
+	assert_eq!(
+		replacer.replace(component.clone()),
+		Component::text("say ")
+			.with_color(TextColor::Gold)
+			.with_extra([
+				Component::text("bye"),
+				Component::empty().with_bold(true),
+				Component::text(" there").with_italic(true),
+			])
+	);
+
+	let replacer = replacer.with_remove_empty(true);
+	assert_eq!(
+		replacer.replace(component),
+		Component::text("say ")
+			.with_color(TextColor::Gold)
+			.with_extra([
+				Component::text("bye"),
+				Component::text(" there").with_italic(true),
+			])
+	);
+
+	// Content other than text breaks up matches.
+	let component =
+		Component::text("hel").with_extra([Component::keybind("key.jump"), Component::text("lo")]);
+	assert_eq!(replacer.replace(component.clone()), component);
+}
+
+#[test]
+fn nested_match_styles() {
+	use std::cell::RefCell;
+	use typewheel::text::TextReplacer;
+	use typewheel::{HoverEvent, Style};
+
+	let styles = RefCell::new(Vec::<Style>::new());
+	let replacer = TextReplacer::literal("hello", |found| {
+		styles.borrow_mut().push(found.style().clone());
+		Component::text("bye")
+	});
+
+	// Translation arguments inherit the effective style of their translation, but hover text
+	// starts out unstyled.
+	let component = Component::text("> ")
+		.with_color(TextColor::Gold)
+		.with_extra([Component::translate(
+			"chat.type.text",
+			[Component::text("hello").with_italic(true)],
+		)
+		.with_bold(true)
+		.with_hover_event(HoverEvent::show_text("hello"))]);
+	replacer.replace(component);
+
+	let mut arg = Style::color(TextColor::Gold);
+	arg.bold = Some(true);
+	arg.italic = Some(true);
+	arg.hover_event = Some(HoverEvent::show_text("hello"));
+
+	assert_eq!(*styles.borrow(), [arg, Style::BLANK]);
+}
+
+#[test]
+fn replacement_limits() {
+	use typewheel::text::TextReplacer;
+
+	let replacer = |every, limit| {
+		TextReplacer::literal("x", |_| Component::text("y"))
+			.with_every(every)
+			.with_limit(limit)
+	};
+
+	let component = Component::text("xx").with_extra([
+		Component::text("xx"),
+		Component::translate("t", [Component::text("xx")]),
+	]);
+
+	assert_eq!(
+		replacer(1, 3).replace(component.clone()),
+		Component::empty().with_extra([
+			Component::text("y"),
+			Component::text("y"),
+			Component::empty().with_extra(["y", "x"]),
+			Component::translate("t", [Component::text("xx")]),
+		])
+	);
+	assert_eq!(
+		replacer(2, 10).replace(component),
+		Component::text("x").with_extra([
+			Component::text("y"),
+			Component::text("x").with_extra(["y"]),
+			Component::translate("t", [Component::text("x").with_extra(["y"])]),
+		])
+	);
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn regex_captures() {
+	use regex::Regex;
+	use typewheel::text::TextReplacer;
+
+	let mentions = TextReplacer::regex(Regex::new(r"@(?<name>\w+)").unwrap(), |found| {
+		Component::text(found.name("name").unwrap()).with_color(TextColor::Aqua)
+	});
+
+	assert_eq!(
+		mentions.replace(Component::text("hi @No").with_extra(["tch!"])),
+		Component::text("hi ").with_extra([
+			Component::text("Notch").with_color(TextColor::Aqua),
+			Component::text("!"),
+		])
+	);
+}
+
+#[test]
+fn styled_runs() {
+	use typewheel::text::StyledRun;
+	use typewheel::Style;
+
+	let component = Component::text("a").with_color(TextColor::Red).with_extra([
+		Component::empty().with_extra(["b"]),
+		Component::keybind("key.jump"),
+		Component::text("c").with_color(TextColor::Blue),
+	]);
+
+	let red = Style::color(TextColor::Red);
+	let runs = component.to_runs();
+	assert_eq!(
+		runs,
+		[
+			StyledRun::text(red.clone(), "ab"),
+			StyledRun::new(red, Content::Keybind("key.jump".into())),
+			StyledRun::text(Style::color(TextColor::Blue), "c"),
+		]
+	);
+
+	let rebuilt = Component::from_runs(runs.clone());
+	assert_eq!(rebuilt.to_runs(), runs);
+	assert_eq!(
+		rebuilt,
+		Component::text("ab")
+			.with_color(TextColor::Red)
+			.with_extra([Component::keybind("key.jump")
+				.with_extra([Component::text("c").with_color(TextColor::Blue)])])
+	);
+}
+
+#[test]
+fn canonical_forms() {
+	use typewheel::HoverEvent;
+
+	let hover = |text: Component| HoverEvent::show_text(text);
+
+	let a = Component::translate("t", [Component::text("x").with_extra(["y"])])
+		.with_hover_event(hover(Component::empty().with_extra(["h"])))
+		.with_extra([Component::text("z").with_color(TextColor::Red)]);
+	let b = Component::empty()
+		.with_hover_event(hover(Component::text("h")))
+		.with_extra([
+			Component::translate("t", [Component::text("xy")]),
+			Component::text("").with_bold(true),
+			Component::empty()
+				.with_color(TextColor::Red)
+				.with_extra(["z"]),
+		]);
+
+	assert!(a.renders_same_as(&b));
+	assert_eq!(a.canonicalize(), b.canonicalize());
+	assert_eq!(a.canonicalize().canonicalize(), a.canonicalize());
+
+	let c = b.clone().with_extra(["!"]);
+	assert!(!a.renders_same_as(&c));
+	assert_ne!(a.canonicalize(), c.canonicalize());
+
+	// Neighbouring runs whose hover text only differs in shape are merged.
+	let a = Component::empty().with_extra([
+		Component::text("a").with_hover_event(hover(Component::text("x").with_extra(["y"]))),
+		Component::text("b").with_hover_event(hover(Component::text("xy"))),
+	]);
+	let b = Component::text("ab").with_hover_event(hover(Component::text("xy")));
+
+	assert!(a.renders_same_as(&b));
+	assert_eq!(a.canonicalize(), b.canonicalize());
+	assert_eq!(a.canonicalize(), b);
+}
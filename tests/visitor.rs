@@ -0,0 +1,109 @@
+use typewheel::visitor::{self, ComponentFolder, ComponentVisitor};
+use typewheel::{ClickEvent, Component, Content, HoverEvent, TextColor};
+
+mod common;
+
+#[derive(Default)]
+struct Recorder(Vec<String>);
+
+impl ComponentVisitor for Recorder {
+	fn visit_text(&mut self, text: &str) {
+		self.0.push(format!("text {text}"));
+	}
+
+	fn visit_translation(&mut self, key: &str, with: &[Component]) {
+		self.0.push(format!("translate {key}"));
+		for arg in with {
+			self.visit_component(arg);
+		}
+	}
+
+	fn visit_color(&mut self, color: &TextColor) {
+		self.0.push(format!("color {color:?}"));
+	}
+
+	fn visit_click_event(&mut self, _event: &ClickEvent) {
+		self.0.push("click".into());
+	}
+}
+
+#[test]
+fn visitor_order() {
+	let component = Component::translate("greeting", [Component::text("a")])
+		.with_color(TextColor::Red)
+		.with_hover_event(HoverEvent::show_text("b"))
+		.with_click_event(ClickEvent::copy("c"))
+		.with_extra(["d"]);
+
+	let mut recorder = Recorder::default();
+	recorder.visit_component(&component);
+
+	assert_eq!(
+		recorder.0,
+		[
+			"color Red",
+			"click",
+			"text b",
+			"translate greeting",
+			"text a",
+			"text d",
+		]
+	);
+}
+
+#[test]
+fn visitor_pruning() {
+	struct TopLevel(usize);
+
+	impl ComponentVisitor for TopLevel {
+		fn visit_component(&mut self, component: &Component) {
+			self.0 += 1;
+			if self.0 == 1 {
+				visitor::walk_component(self, component);
+			}
+		}
+	}
+
+	let mut counter = TopLevel(0);
+	counter.visit_component(&common::deeply_nested());
+	assert_eq!(counter.0, 3);
+}
+
+#[test]
+fn folding() {
+	struct Redact;
+
+	impl ComponentFolder for Redact {
+		fn fold_text(&mut self, text: String) -> Content {
+			Content::Text("*".repeat(text.len()))
+		}
+
+		fn fold_keybind(&mut self, _key: String) -> Content {
+			Content::Text("?".into())
+		}
+
+		fn fold_click_event(&mut self, _event: ClickEvent) -> Option<ClickEvent> {
+			None
+		}
+	}
+
+	let component = Component::text("abc")
+		.with_click_event(ClickEvent::copy("abc"))
+		.with_hover_event(HoverEvent::show_text("de"))
+		.with_extra([Component::keybind("key.jump").with_color(TextColor::Gold)]);
+
+	assert_eq!(
+		Redact.fold_component(component),
+		Component::text("***")
+			.with_hover_event(HoverEvent::show_text("**"))
+			.with_extra([Component::text("?").with_color(TextColor::Gold)])
+	);
+
+	struct Identity;
+	impl ComponentFolder for Identity {}
+
+	assert_eq!(
+		Identity.fold_component(common::styled_hello()),
+		common::styled_hello()
+	);
+}